@@ -3,9 +3,9 @@
 use std::hint::black_box;
 
 use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
-#[cfg(feature = "cpp")]
-use fastpfor::AnyLenCodec;
-use fastpfor::{BlockCodec as _, FastPForBlock128, FastPForBlock256, slice_to_blocks};
+use fastpfor::{
+    AnyLenCodec, BlockCodec as _, FastPForBlock128, FastPForBlock256, VariableByte, slice_to_blocks,
+};
 
 // Shared helpers live in `src/bench_utils.rs` (library exposes the same file only under `cfg(test)`).
 #[path = "../src/test_utils.rs"]
@@ -13,14 +13,18 @@ mod test_utils;
 #[cfg(feature = "cpp")]
 use fastpfor::cpp::CppFastPFor128;
 use test_utils::{
-    BlockSizeFixture, compress_fixtures, generate_uniform_data_small_value_distribution,
-    ratio_fixtures,
+    BlockSizeFixture, block_compress, compress, compress_fixtures,
+    generate_uniform_data_small_value_distribution, ratio_fixtures,
 };
 
 /// Number of blocks per benchmark run.  The element count per run is
 /// `BLOCK_COUNTS[i] * C::elements_per_block()`, e.g. 8 × 128 = 1,024 or 32 × 128 = 4,096.
 const BLOCK_COUNTS: &[usize] = &[8, 32];
 
+/// Block counts for [`benchmark_decode_large`], large enough that a memset of the
+/// output buffer shows up in the timings.
+const LARGE_BLOCK_COUNTS: &[usize] = &[1024, 8192];
+
 fn benchmark_compression(c: &mut Criterion) {
     let mut group = c.benchmark_group("compression");
     for (bc, fix) in compress_fixtures::<FastPForBlock128>(BLOCK_COUNTS) {
@@ -179,6 +183,85 @@ fn benchmark_block_sizes(c: &mut Criterion) {
     group.finish();
 }
 
+/// Decodes large pages into a fresh `Vec` each iteration, so the cost of
+/// preparing the output buffer is included. Decoders write each value once,
+/// without zero-filling the buffer first.
+#[expect(
+    clippy::slow_vector_initialization,
+    reason = "the zero-filled baselines time an explicit memset, which `vec![0; n]` would skip"
+)]
+fn benchmark_decode_large(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_large");
+    for &bc in LARGE_BLOCK_COUNTS {
+        let data = generate_uniform_data_small_value_distribution(bc * FastPForBlock128::size());
+        group.throughput(Throughput::Elements(data.len() as u64));
+
+        let compressed = block_compress::<FastPForBlock128>(&data).unwrap();
+        let expected = u32::try_from(data.len()).expect("expected_values fits in u32");
+        group.bench_with_input(
+            BenchmarkId::new("fastpfor128", bc),
+            &compressed,
+            |b, input| {
+                let mut codec = FastPForBlock128::default();
+                b.iter(|| {
+                    let mut out = Vec::new();
+                    codec
+                        .decode_blocks(black_box(input), Some(expected), &mut out)
+                        .unwrap();
+                    black_box(out.len())
+                });
+            },
+        );
+        // Baseline: zero-fill the output first, as decoding did before it
+        // wrote into spare capacity.
+        group.bench_with_input(
+            BenchmarkId::new("fastpfor128_zero_filled", bc),
+            &compressed,
+            |b, input| {
+                let mut codec = FastPForBlock128::default();
+                b.iter(|| {
+                    let mut out = Vec::new();
+                    out.resize(data.len(), 0);
+                    codec
+                        .decode_blocks_into(black_box(input), Some(expected), &mut out)
+                        .unwrap();
+                    black_box(out.len())
+                });
+            },
+        );
+
+        let compressed = compress::<VariableByte>(&data).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("variable_byte", bc),
+            &compressed,
+            |b, input| {
+                let mut codec = VariableByte::new();
+                b.iter(|| {
+                    let mut out: Vec<u32> = Vec::new();
+                    codec
+                        .decode(black_box(input), &mut out, Some(expected))
+                        .unwrap();
+                    black_box(out.len())
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("variable_byte_zero_filled", bc),
+            &compressed,
+            |b, input| {
+                let mut codec = VariableByte::new();
+                b.iter(|| {
+                    let mut out: Vec<u32> = Vec::new();
+                    out.resize(data.len(), 0);
+                    codec.decode_into(black_box(input), &mut out).unwrap();
+                    black_box(out.len())
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
 fn benchmark_compression_ratio(c: &mut Criterion) {
     let mut group = c.benchmark_group("compression_ratio");
     group.sample_size(20);
@@ -292,6 +375,7 @@ criterion_group!(
     benchmark_decompression,
     benchmark_roundtrip,
     benchmark_block_sizes,
+    benchmark_decode_large,
     benchmark_compression_ratio,
 );
 
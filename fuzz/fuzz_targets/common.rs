@@ -3,7 +3,7 @@
 #![allow(dead_code)]
 
 use fastpfor::cpp::*;
-use fastpfor::{AnyLenCodec, FastPFor128, FastPFor256, FastPForResult, JustCopy, VariableByte};
+use fastpfor::{DynCodec, FastPFor128, FastPFor256, JustCopy, VariableByte};
 
 // ── Debug helper ─────────────────────────────────────────────────────────────
 
@@ -37,85 +37,28 @@ pub struct FuzzInput<C> {
     pub codec: C,
 }
 
-// `AnyLenCodec` is not dyn-compatible; carry a closed enum of every fuzzed codec instead.
-macro_rules! define_fuzz_any_len {
-    ($( $variant:ident ( $ty:ty ) ),* $(,)?) => {
-        pub enum FuzzAnyLen {
-            $( $variant($ty), )*
-        }
-
-        impl FuzzAnyLen {
-            pub fn encode(&mut self, input: &[u32], out: &mut Vec<u32>) -> FastPForResult<()> {
-                match self {
-                    $( Self::$variant(codec) => codec.encode(input, out), )*
-                }
-            }
-
-            pub fn decode(
-                &mut self,
-                input: &[u32],
-                out: &mut Vec<u32>,
-                expected_len: Option<u32>,
-            ) -> FastPForResult<()> {
-                match self {
-                    $( Self::$variant(codec) => codec.decode(input, out, expected_len), )*
-                }
-            }
-        }
-    };
-}
-
-define_fuzz_any_len!(
-    FastPFor256(FastPFor256),
-    FastPFor128(FastPFor128),
-    VariableByte(VariableByte),
-    JustCopy(JustCopy),
-    CppBP32(CppBP32),
-    CppCopy(CppCopy),
-    CppFastBinaryPacking8(CppFastBinaryPacking8),
-    CppFastPFor128(CppFastPFor128),
-    CppFastPFor256(CppFastPFor256),
-    CppFastBinaryPacking16(CppFastBinaryPacking16),
-    CppFastBinaryPacking32(CppFastBinaryPacking32),
-    CppMaskedVByte(CppMaskedVByte),
-    CppNewPFor(CppNewPFor),
-    CppOptPFor(CppOptPFor),
-    CppPFor2008(CppPFor2008),
-    CppPFor(CppPFor),
-    CppSimdBinaryPacking(CppSimdBinaryPacking),
-    CppSimdFastPFor128(CppSimdFastPFor128),
-    CppSimdFastPFor256(CppSimdFastPFor256),
-    CppSimdGroupSimple(CppSimdGroupSimple),
-    CppSimdGroupSimpleRingBuf(CppSimdGroupSimpleRingBuf),
-    CppSimdNewPFor(CppSimdNewPFor),
-    CppSimdOptPFor(CppSimdOptPFor),
-    CppSimdPFor(CppSimdPFor),
-    CppSimdSimplePFor(CppSimdSimplePFor),
-    CppStreamVByte(CppStreamVByte),
-    CppVByte(CppVByte),
-    CppVarInt(CppVarInt),
-    CppVarIntGb(CppVarIntGb),
-);
+/// Any fuzzed codec, Rust or C++, selected at runtime.
+pub type FuzzAnyLen = Box<dyn DynCodec>;
 
 pub type CodecEntry = (&'static str, fn() -> FuzzAnyLen);
 
 macro_rules! codec_ctor_fn {
-    ($fn_name:ident, $variant:ident, $ty:ty) => {
+    ($fn_name:ident, $ty:ty) => {
         fn $fn_name() -> FuzzAnyLen {
-            FuzzAnyLen::$variant(<$ty>::default())
+            Box::new(<$ty>::default())
         }
     };
 }
 
-codec_ctor_fn!(make_rust_fastpfor256, FastPFor256, FastPFor256);
-codec_ctor_fn!(make_rust_fastpfor128, FastPFor128, FastPFor128);
+codec_ctor_fn!(make_rust_fastpfor256, FastPFor256);
+codec_ctor_fn!(make_rust_fastpfor128, FastPFor128);
 
 fn make_rust_variable_byte() -> FuzzAnyLen {
-    FuzzAnyLen::VariableByte(VariableByte)
+    Box::new(VariableByte)
 }
 
 fn make_rust_just_copy() -> FuzzAnyLen {
-    FuzzAnyLen::JustCopy(JustCopy)
+    Box::new(JustCopy)
 }
 
 /// Rust codecs. Block codecs are wrapped in `CompositeCodec<_, VariableByte>`.
@@ -126,67 +69,34 @@ pub static RUST: &[CodecEntry] = &[
     ("JustCopy", make_rust_just_copy),
 ];
 
-codec_ctor_fn!(make_cpp_bp32, CppBP32, CppBP32);
-codec_ctor_fn!(make_cpp_copy, CppCopy, CppCopy);
-codec_ctor_fn!(
-    make_cpp_fast_binary_packing8,
-    CppFastBinaryPacking8,
-    CppFastBinaryPacking8
-);
-codec_ctor_fn!(make_cpp_fastpfor128, CppFastPFor128, CppFastPFor128);
-codec_ctor_fn!(make_cpp_fastpfor256, CppFastPFor256, CppFastPFor256);
-codec_ctor_fn!(
-    make_cpp_fast_binary_packing16,
-    CppFastBinaryPacking16,
-    CppFastBinaryPacking16
-);
-codec_ctor_fn!(
-    make_cpp_fast_binary_packing32,
-    CppFastBinaryPacking32,
-    CppFastBinaryPacking32
-);
-codec_ctor_fn!(make_cpp_masked_vbyte, CppMaskedVByte, CppMaskedVByte);
-codec_ctor_fn!(make_cpp_new_pfor, CppNewPFor, CppNewPFor);
-codec_ctor_fn!(make_cpp_opt_pfor, CppOptPFor, CppOptPFor);
-codec_ctor_fn!(make_cpp_pfor2008, CppPFor2008, CppPFor2008);
-codec_ctor_fn!(make_cpp_pfor, CppPFor, CppPFor);
-codec_ctor_fn!(
-    make_cpp_simd_binary_packing,
-    CppSimdBinaryPacking,
-    CppSimdBinaryPacking
-);
-codec_ctor_fn!(
-    make_cpp_simd_fastpfor128,
-    CppSimdFastPFor128,
-    CppSimdFastPFor128
-);
-codec_ctor_fn!(
-    make_cpp_simd_fastpfor256,
-    CppSimdFastPFor256,
-    CppSimdFastPFor256
-);
-codec_ctor_fn!(
-    make_cpp_simd_group_simple,
-    CppSimdGroupSimple,
-    CppSimdGroupSimple
-);
+codec_ctor_fn!(make_cpp_bp32, CppBP32);
+codec_ctor_fn!(make_cpp_copy, CppCopy);
+codec_ctor_fn!(make_cpp_fast_binary_packing8, CppFastBinaryPacking8);
+codec_ctor_fn!(make_cpp_fastpfor128, CppFastPFor128);
+codec_ctor_fn!(make_cpp_fastpfor256, CppFastPFor256);
+codec_ctor_fn!(make_cpp_fast_binary_packing16, CppFastBinaryPacking16);
+codec_ctor_fn!(make_cpp_fast_binary_packing32, CppFastBinaryPacking32);
+codec_ctor_fn!(make_cpp_masked_vbyte, CppMaskedVByte);
+codec_ctor_fn!(make_cpp_new_pfor, CppNewPFor);
+codec_ctor_fn!(make_cpp_opt_pfor, CppOptPFor);
+codec_ctor_fn!(make_cpp_pfor2008, CppPFor2008);
+codec_ctor_fn!(make_cpp_pfor, CppPFor);
+codec_ctor_fn!(make_cpp_simd_binary_packing, CppSimdBinaryPacking);
+codec_ctor_fn!(make_cpp_simd_fastpfor128, CppSimdFastPFor128);
+codec_ctor_fn!(make_cpp_simd_fastpfor256, CppSimdFastPFor256);
+codec_ctor_fn!(make_cpp_simd_group_simple, CppSimdGroupSimple);
 codec_ctor_fn!(
     make_cpp_simd_group_simple_ring_buf,
-    CppSimdGroupSimpleRingBuf,
     CppSimdGroupSimpleRingBuf
 );
-codec_ctor_fn!(make_cpp_simd_new_pfor, CppSimdNewPFor, CppSimdNewPFor);
-codec_ctor_fn!(make_cpp_simd_opt_pfor, CppSimdOptPFor, CppSimdOptPFor);
-codec_ctor_fn!(make_cpp_simd_pfor, CppSimdPFor, CppSimdPFor);
-codec_ctor_fn!(
-    make_cpp_simd_simple_pfor,
-    CppSimdSimplePFor,
-    CppSimdSimplePFor
-);
-codec_ctor_fn!(make_cpp_stream_vbyte, CppStreamVByte, CppStreamVByte);
-codec_ctor_fn!(make_cpp_vbyte, CppVByte, CppVByte);
-codec_ctor_fn!(make_cpp_var_int, CppVarInt, CppVarInt);
-codec_ctor_fn!(make_cpp_var_int_gb, CppVarIntGb, CppVarIntGb);
+codec_ctor_fn!(make_cpp_simd_new_pfor, CppSimdNewPFor);
+codec_ctor_fn!(make_cpp_simd_opt_pfor, CppSimdOptPFor);
+codec_ctor_fn!(make_cpp_simd_pfor, CppSimdPFor);
+codec_ctor_fn!(make_cpp_simd_simple_pfor, CppSimdSimplePFor);
+codec_ctor_fn!(make_cpp_stream_vbyte, CppStreamVByte);
+codec_ctor_fn!(make_cpp_vbyte, CppVByte);
+codec_ctor_fn!(make_cpp_var_int, CppVarInt);
+codec_ctor_fn!(make_cpp_var_int_gb, CppVarIntGb);
 
 /// C++ codecs (any-length; block codecs are already composites in the C++ library).
 pub static CPP: &[CodecEntry] = &[
@@ -249,28 +159,28 @@ pub struct CodecPair {
 }
 
 fn pair_rust_fastpfor128() -> FuzzAnyLen {
-    FuzzAnyLen::FastPFor128(FastPFor128::default())
+    Box::new(FastPFor128::default())
 }
 fn pair_cpp_fastpfor128() -> FuzzAnyLen {
-    FuzzAnyLen::CppFastPFor128(CppFastPFor128::default())
+    Box::new(CppFastPFor128::default())
 }
 fn pair_rust_fastpfor256() -> FuzzAnyLen {
-    FuzzAnyLen::FastPFor256(FastPFor256::default())
+    Box::new(FastPFor256::default())
 }
 fn pair_cpp_fastpfor256() -> FuzzAnyLen {
-    FuzzAnyLen::CppFastPFor256(CppFastPFor256::default())
+    Box::new(CppFastPFor256::default())
 }
 fn pair_rust_variable_byte() -> FuzzAnyLen {
-    FuzzAnyLen::VariableByte(VariableByte)
+    Box::new(VariableByte)
 }
 fn pair_cpp_var_int() -> FuzzAnyLen {
-    FuzzAnyLen::CppVarInt(CppVarInt::default())
+    Box::new(CppVarInt::default())
 }
 fn pair_rust_just_copy() -> FuzzAnyLen {
-    FuzzAnyLen::JustCopy(JustCopy)
+    Box::new(JustCopy)
 }
 fn pair_cpp_copy() -> FuzzAnyLen {
-    FuzzAnyLen::CppCopy(CppCopy::default())
+    Box::new(CppCopy::default())
 }
 
 /// Pairs of Rust and C++ codecs expected to produce bit-identical output.
@@ -16,12 +16,12 @@ fuzz_target!(|data: FuzzInput<AnyLenSelector>| {
     let input = &data.data;
     let mut compressed = Vec::new();
     codec
-        .encode(input, &mut compressed)
+        .encode_dyn(input, &mut compressed)
         .expect("C++ compression failed");
 
     let mut decompressed = Vec::new();
     codec
-        .decode(&compressed, &mut decompressed, None)
+        .decode_dyn(&compressed, &mut decompressed, None)
         .expect("C++ decompression failed");
 
     assert_eq!(
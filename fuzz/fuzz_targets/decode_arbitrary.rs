@@ -57,5 +57,5 @@ fuzz_target!(|data: FuzzInput| {
 
     // The decoder must either succeed or return an error — a panic is a bug.
     let mut output = Vec::new();
-    let _ = codec.decode(&compressed, &mut output, None);
+    let _ = codec.decode_dyn(&compressed, &mut output, None);
 });
@@ -36,22 +36,22 @@ fuzz_target!(|data: FuzzInput<CompatSelector>| {
 
     // Rust roundtrip
     let mut rust_compressed = Vec::new();
-    if rust_codec.encode(input, &mut rust_compressed).is_err() {
+    if rust_codec.encode_dyn(input, &mut rust_compressed).is_err() {
         return;
     }
     let mut rust_decompressed = Vec::new();
     rust_codec
-        .decode(&rust_compressed, &mut rust_decompressed, None)
+        .decode_dyn(&rust_compressed, &mut rust_decompressed, None)
         .expect("Rust decompress of self-compressed data must not fail");
 
     // C++ roundtrip (independent oracle)
     let mut cpp_compressed = Vec::new();
     cpp_codec
-        .encode(input, &mut cpp_compressed)
+        .encode_dyn(input, &mut cpp_compressed)
         .expect("C++ compression failed");
     let mut cpp_decompressed = Vec::new();
     cpp_codec
-        .decode(&cpp_compressed, &mut cpp_decompressed, None)
+        .decode_dyn(&cpp_compressed, &mut cpp_decompressed, None)
         .expect("C++ decompression failed");
 
     assert_eq!(
@@ -26,12 +26,12 @@ fuzz_target!(|data: FuzzInput<PairSelector>| {
 
     let mut rust_out = Vec::new();
     rust_codec
-        .encode(&data.data, &mut rust_out)
+        .encode_dyn(&data.data, &mut rust_out)
         .expect("Rust encode failed");
 
     let mut cpp_out = Vec::new();
     cpp_codec
-        .encode(&data.data, &mut cpp_out)
+        .encode_dyn(&data.data, &mut cpp_out)
         .expect("C++ encode must not fail when Rust encode succeeded");
 
     assert_eq!(
@@ -42,7 +42,7 @@ fuzz_target!(|data: FuzzInput<PairSelector>| {
 
     let mut decoded = Vec::new();
     rust_codec
-        .decode(&rust_out, &mut decoded, None)
+        .decode_dyn(&rust_out, &mut decoded, None)
         .expect("Rust decode of self-compressed data must not fail");
     assert_eq!(
         decoded, data.data,
@@ -52,7 +52,7 @@ fuzz_target!(|data: FuzzInput<PairSelector>| {
 
     decoded.truncate(0);
     cpp_codec
-        .decode(&cpp_out, &mut decoded, None)
+        .decode_dyn(&cpp_out, &mut decoded, None)
         .expect("C++ decode of self-compressed data must not fail");
     assert_eq!(
         decoded, data.data,
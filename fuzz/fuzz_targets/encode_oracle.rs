@@ -19,13 +19,13 @@ fuzz_target!(|data: FuzzInput<AnyLenSelector>| {
 
     let input = &data.data;
     let mut compressed = Vec::new();
-    if codec.encode(input, &mut compressed).is_err() {
+    if codec.encode_dyn(input, &mut compressed).is_err() {
         return;
     }
 
     let mut decompressed = Vec::new();
     codec
-        .decode(&compressed, &mut decompressed, None)
+        .decode_dyn(&compressed, &mut decompressed, None)
         .expect("Rust decompress of self-compressed data must not fail");
 
     assert_eq!(
@@ -0,0 +1,346 @@
+//! [`ChunkedCodec`]: lifts the `u32` count limits of a single encode call by
+//! splitting the input into independently encoded segments.
+
+use alloc::vec::Vec;
+use core::num::NonZeroU32;
+
+use crate::codec::AnyLenCodec;
+use crate::helpers::AsUsize;
+use crate::segments::{next_segment, split_header};
+use crate::{Element, FastPForError, FastPForResult};
+
+/// Default number of values per segment.
+///
+/// Small enough that even a 64-bit segment's worst-case encoding stays well
+/// below `u32::MAX` words, and a multiple of every `FastPFor` block size.
+const DEFAULT_SEGMENT_LEN: u32 = 1 << 28;
+
+/// Number of `u32` words in the stream header.
+const HEADER_WORDS: usize = 3;
+
+/// Wraps any [`AnyLenCodec`] so that one call can encode or decode more than
+/// `u32::MAX` values.
+///
+/// Block headers, `expected_len` hints, and the internal cursors of the inner
+/// codecs are all `u32`, which caps a single encode call at about 4 Gi values.
+/// `ChunkedCodec` splits the input into segments of at most `segment_len`
+/// values, encodes each segment with the inner codec, and records the total as
+/// a 64-bit count.
+///
+/// # Wire format
+///
+/// ```text
+/// [ count: u64 (low word, high word) ] [ segment_len: u32 ]
+/// [ words: u32 ] [ segment 0 encoded data... ]
+/// [ words: u32 ] [ segment 1 encoded data... ]
+/// ...
+/// ```
+///
+/// Every segment but the last holds exactly `segment_len` values. Because the
+/// segment length is stored in the header, a decoder does not need the
+/// encoder's configuration.
+///
+/// # Example
+///
+/// ```
+/// use fastpfor::{AnyLenCodec, ChunkedCodec, FastPFor256};
+///
+/// let data: Vec<u32> = (0..1000).collect();
+/// let mut codec = ChunkedCodec::<FastPFor256>::default();
+///
+/// let mut encoded = Vec::new();
+/// codec.encode(&data, &mut encoded).unwrap();
+///
+/// let mut decoded = Vec::new();
+/// codec.decode_large(&encoded, &mut decoded, Some(1000)).unwrap();
+/// assert_eq!(decoded, data);
+/// ```
+#[derive(Debug)]
+pub struct ChunkedCodec<C> {
+    inner: C,
+    segment_len: u32,
+}
+
+impl<C: Default> Default for ChunkedCodec<C> {
+    fn default() -> Self {
+        Self::new(C::default())
+    }
+}
+
+impl<C> ChunkedCodec<C> {
+    /// Creates a `ChunkedCodec` with the default segment length (2<sup>28</sup> values).
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            segment_len: DEFAULT_SEGMENT_LEN,
+        }
+    }
+
+    /// Creates a `ChunkedCodec` that encodes at most `segment_len` values per segment.
+    ///
+    /// Prefer a multiple of the inner codec's block size, so that only the last
+    /// segment needs a tail encoding. `FastPFor` rejects segments whose
+    /// worst-case encoding would exceed `u32::MAX` words, so keep `segment_len`
+    /// below about 2<sup>31</sup> values (2<sup>30</sup> for 64-bit values).
+    pub fn with_segment_len(inner: C, segment_len: NonZeroU32) -> Self {
+        Self {
+            inner,
+            segment_len: segment_len.get(),
+        }
+    }
+
+    /// Decompresses a stream written by [`encode`](AnyLenCodec::encode),
+    /// taking a 64-bit `expected_len`.
+    ///
+    /// This is the counterpart of [`AnyLenCodec::decode`] for streams that may
+    /// hold more than `u32::MAX` values; the checks are otherwise identical.
+    pub fn decode_large<T: Element>(
+        &mut self,
+        input: &[u32],
+        out: &mut Vec<T>,
+        expected_len: Option<u64>,
+    ) -> FastPForResult<()>
+    where
+        C: AnyLenCodec<T>,
+    {
+        let (count, segment_len, mut rest) = read_header(input)?;
+        if let Some(expected) = expected_len {
+            if count != expected {
+                return Err(FastPForError::DecodedCountMismatch {
+                    actual: usize::try_from(count).unwrap_or(usize::MAX),
+                    expected: usize::try_from(expected).unwrap_or(usize::MAX),
+                });
+            }
+        }
+        let max = <Self as AnyLenCodec<T>>::max_decompressed_len(input.len());
+        let count = usize::try_from(count)
+            .ok()
+            .filter(|&count| count <= max)
+            .ok_or(FastPForError::NotEnoughData)?;
+
+        out.reserve(count);
+        let mut remaining = count;
+        while remaining > 0 {
+            let values = remaining.min(segment_len.as_usize());
+            let segment = next_segment(&mut rest)?;
+            // `values <= segment_len`, which is a `u32`.
+            self.inner.decode(segment, out, Some(values as u32))?;
+            remaining -= values;
+        }
+        if !rest.is_empty() {
+            return Err(FastPForError::InvalidInputLength(input.len()));
+        }
+        Ok(())
+    }
+}
+
+/// Splits a stream into its value count, segment length, and segment entries.
+fn read_header(input: &[u32]) -> FastPForResult<(u64, u32, &[u32])> {
+    let (header, rest) = split_header::<HEADER_WORDS>(input)?;
+    let count = u64::from(header[0]) | (u64::from(header[1]) << 32);
+    let segment_len = header[2];
+    if segment_len == 0 && count != 0 {
+        return Err(FastPForError::NotEnoughData);
+    }
+    Ok((count, segment_len, rest))
+}
+
+impl<T: Element, C: AnyLenCodec<T>> AnyLenCodec<T> for ChunkedCodec<C> {
+    fn encode(&mut self, input: &[T], out: &mut Vec<u32>) -> FastPForResult<()> {
+        let count = input.len() as u64;
+        out.extend([count as u32, (count >> 32) as u32, self.segment_len]);
+        for segment in input.chunks(self.segment_len.as_usize()) {
+            let words_pos = out.len();
+            out.push(0);
+            self.inner.encode(segment, out)?;
+            let words = out.len() - words_pos - 1;
+            out[words_pos] =
+                u32::try_from(words).map_err(|_| FastPForError::InvalidInputLength(words))?;
+        }
+        Ok(())
+    }
+
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        let segment_len = self.segment_len.as_usize();
+        let full = n_values / segment_len;
+        let remainder = n_values % segment_len;
+        let mut bound = HEADER_WORDS + full * (1 + self.inner.max_compressed_len(segment_len));
+        if remainder > 0 {
+            bound += 1 + self.inner.max_compressed_len(remainder);
+        }
+        bound
+    }
+
+    fn encode_into(&mut self, input: &[T], out: &mut [u32]) -> FastPForResult<usize> {
+        let count = input.len() as u64;
+        let Some(header) = out.first_chunk_mut::<HEADER_WORDS>() else {
+            return Err(FastPForError::OutputBufferTooSmall);
+        };
+        *header = [count as u32, (count >> 32) as u32, self.segment_len];
+        let mut pos = HEADER_WORDS;
+        for segment in input.chunks(self.segment_len.as_usize()) {
+            let Some((words, rest)) = out[pos..].split_first_mut() else {
+                return Err(FastPForError::OutputBufferTooSmall);
+            };
+            let n = self.inner.encode_into(segment, rest)?;
+            *words = u32::try_from(n).map_err(|_| FastPForError::InvalidInputLength(n))?;
+            pos += 1 + n;
+        }
+        Ok(pos)
+    }
+
+    fn decode(
+        &mut self,
+        input: &[u32],
+        out: &mut Vec<T>,
+        expected_len: Option<u32>,
+    ) -> FastPForResult<()> {
+        self.decode_large(input, out, expected_len.map(u64::from))
+    }
+
+    fn decode_into(&mut self, input: &[u32], out: &mut [T]) -> FastPForResult<usize> {
+        let (count, segment_len, mut rest) = read_header(input)?;
+        let count = usize::try_from(count)
+            .ok()
+            .filter(|&count| count <= out.len())
+            .ok_or(FastPForError::OutputBufferTooSmall)?;
+
+        let mut written = 0;
+        while written < count {
+            let values = (count - written).min(segment_len.as_usize());
+            let segment = next_segment(&mut rest)?;
+            let n = self
+                .inner
+                .decode_into(segment, &mut out[written..written + values])?;
+            n.is_decoded_mismatch(values)?;
+            written += n;
+        }
+        if !rest.is_empty() {
+            return Err(FastPForError::InvalidInputLength(input.len()));
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(all(test, feature = "rust"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{compress, decompress, roundtrip, roundtrip_elements, roundtrip_into};
+    use crate::{FastPFor128, FastPFor256, VariableByte};
+
+    fn small_segments<C: Default>(segment_len: u32) -> ChunkedCodec<C> {
+        ChunkedCodec::with_segment_len(C::default(), NonZeroU32::new(segment_len).unwrap())
+    }
+
+    #[test]
+    fn test_empty_input_is_header_only() {
+        let encoded = compress::<ChunkedCodec<FastPFor256>>(&[]).unwrap();
+        assert_eq!(encoded, [0, 0, DEFAULT_SEGMENT_LEN]);
+        roundtrip::<ChunkedCodec<FastPFor256>>(&[]);
+    }
+
+    #[test]
+    fn test_single_segment_roundtrip() {
+        let data: Vec<u32> = (0..1000).collect();
+        roundtrip::<ChunkedCodec<FastPFor128>>(&data);
+    }
+
+    #[test]
+    fn test_many_segments_roundtrip() {
+        let data: Vec<u32> = (0..5000u32).map(|i| i * 7 % 1013).collect();
+        let mut codec = small_segments::<FastPFor256>(512);
+        let mut encoded = Vec::new();
+        codec.encode(&data, &mut encoded).unwrap();
+        assert_eq!(encoded[..3], [5000, 0, 512]);
+
+        // The stored segment length wins over the decoder's own configuration.
+        let mut decoded = Vec::new();
+        ChunkedCodec::<FastPFor256>::default()
+            .decode_large(&encoded, &mut decoded, Some(5000))
+            .unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_wide_elements_roundtrip() {
+        let data: Vec<u64> = (0..700u64).map(|i| i << 33).collect();
+        roundtrip_elements::<u64, ChunkedCodec<VariableByte>>(&data);
+    }
+
+    #[test]
+    fn test_decode_expected_len_mismatch_errors() {
+        let encoded = compress::<ChunkedCodec<FastPFor256>>(&[1, 2, 3]).unwrap();
+        let err = decompress::<ChunkedCodec<FastPFor256>>(&encoded, Some(4)).unwrap_err();
+        assert!(matches!(
+            err,
+            FastPForError::DecodedCountMismatch {
+                actual: 3,
+                expected: 4
+            }
+        ));
+    }
+
+    #[test]
+    fn test_decode_count_beyond_u32_is_rejected_without_allocating() {
+        let err = decompress::<ChunkedCodec<FastPFor256>>(&[0, 1, 1024, 0], None).unwrap_err();
+        assert!(matches!(err, FastPForError::NotEnoughData));
+    }
+
+    #[test]
+    fn test_decode_truncated_segment_errors() {
+        let data: Vec<u32> = (0..600).collect();
+        let mut encoded = Vec::new();
+        small_segments::<FastPFor256>(256)
+            .encode(&data, &mut encoded)
+            .unwrap();
+        encoded.pop();
+        decompress::<ChunkedCodec<FastPFor256>>(&encoded, None).unwrap_err();
+    }
+
+    #[test]
+    fn test_decode_trailing_words_error() {
+        let mut encoded = compress::<ChunkedCodec<FastPFor256>>(&[1, 2, 3]).unwrap();
+        encoded.push(0);
+        let err = decompress::<ChunkedCodec<FastPFor256>>(&encoded, None).unwrap_err();
+        assert!(matches!(err, FastPForError::InvalidInputLength(_)));
+    }
+
+    #[test]
+    fn test_decode_short_header_errors() {
+        decompress::<ChunkedCodec<FastPFor256>>(&[0, 0], None).unwrap_err();
+    }
+
+    #[test]
+    fn test_decode_into_slice_across_segments() {
+        let data: Vec<u32> = (0..1500u32).map(|i| i * 7 % 1013).collect();
+        let mut encoded = Vec::new();
+        small_segments::<FastPFor128>(512)
+            .encode(&data, &mut encoded)
+            .unwrap();
+        let mut out = vec![0; 1600];
+        let written = ChunkedCodec::<FastPFor128>::default()
+            .decode_into(&encoded, &mut out)
+            .unwrap();
+        assert_eq!(&out[..written], data);
+
+        roundtrip_into::<u32, ChunkedCodec<FastPFor256>>(&data);
+    }
+
+    #[test]
+    fn test_encode_into_bound_across_segments() {
+        let data: Vec<u32> = (0..1300u32)
+            .map(|i| i.wrapping_mul(2_654_435_761))
+            .collect();
+        let mut codec = small_segments::<FastPFor128>(512);
+        let mut expected = Vec::new();
+        codec.encode(&data, &mut expected).unwrap();
+
+        let bound = AnyLenCodec::<u32>::max_compressed_len(&codec, data.len());
+        let mut out = vec![0; bound];
+        let written = codec.encode_into(&data, &mut out).unwrap();
+        assert_eq!(out[..written], expected);
+
+        let err = codec.encode_into(&data, &mut out[..2]).unwrap_err();
+        assert!(matches!(err, FastPForError::OutputBufferTooSmall));
+    }
+}
@@ -1,6 +1,10 @@
-use bytemuck::{Pod, cast_slice};
+#[cfg(feature = "rust")]
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
 
-use crate::FastPForResult;
+use bytemuck::{Pod, cast_slice, try_cast_slice};
+
+use crate::{Element, FastPForError, FastPForResult};
 
 /// Internal default for max decompressed length. Used by trait defaults and C++ FFI.
 #[inline]
@@ -8,7 +12,87 @@ pub(crate) fn default_max_decoded_len(compressed_words: usize) -> usize {
     compressed_words.saturating_mul(1024)
 }
 
-/// Compresses and decompresses fixed-size blocks of `u32` values.
+/// Internal default for max compressed length, given the number of input `u32` words.
+/// Used by trait defaults and C++ FFI, which have no proven worst case.
+#[inline]
+pub(crate) fn default_max_encoded_len(input_words: usize) -> usize {
+    input_words.saturating_mul(2).saturating_add(1024)
+}
+
+/// Encodes into `out` through an `encode_into`-style closure: reserves `bound`
+/// words, lets `f` fill them, and keeps only the words it reports as written.
+pub(crate) fn encode_with_bound(
+    out: &mut Vec<u32>,
+    bound: usize,
+    f: impl FnOnce(&mut [u32]) -> FastPForResult<usize>,
+) -> FastPForResult<()> {
+    let start = out.len();
+    out.resize(start + bound, 0);
+    let result = f(&mut out[start..]);
+    out.truncate(start + *result.as_ref().unwrap_or(&0));
+    result.map(|_| ())
+}
+
+/// Appends `words` to `out` as little-endian bytes.
+pub(crate) fn extend_le_bytes(words: &[u32], out: &mut Vec<u8>) {
+    if cfg!(target_endian = "little") {
+        out.extend_from_slice(cast_slice(words));
+    } else {
+        out.reserve(words.len() * 4);
+        for word in words {
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+    }
+}
+
+/// Views `words` as little-endian bytes, borrowing them on little-endian
+/// targets and copying them otherwise.
+#[cfg(feature = "rust")]
+pub(crate) fn le_bytes(words: &[u32]) -> Cow<'_, [u8]> {
+    if cfg!(target_endian = "little") {
+        Cow::Borrowed(cast_slice(words))
+    } else {
+        let mut bytes = Vec::new();
+        extend_le_bytes(words, &mut bytes);
+        Cow::Owned(bytes)
+    }
+}
+
+/// Calls `f` with `bytes` read as little-endian `u32` words.
+///
+/// Borrows `bytes` in place when it is 4-byte aligned on a little-endian
+/// target, and copies it otherwise.
+pub(crate) fn with_le_words<R>(
+    bytes: &[u8],
+    f: impl FnOnce(&[u32]) -> FastPForResult<R>,
+) -> FastPForResult<R> {
+    if bytes.len() % 4 != 0 {
+        return Err(FastPForError::InvalidInputLength(bytes.len()));
+    }
+    if cfg!(target_endian = "little") {
+        if let Ok(words) = try_cast_slice(bytes) {
+            return f(words);
+        }
+    }
+    let words: Vec<u32> = bytes
+        .chunks_exact(4)
+        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect();
+    f(&words)
+}
+
+/// Copies `values` to the start of `out`, returning `values.len()`.
+fn copy_into<T: Element>(values: &[T], out: &mut [T]) -> FastPForResult<usize> {
+    out.get_mut(..values.len())
+        .ok_or(FastPForError::OutputBufferTooSmall)?
+        .copy_from_slice(values);
+    Ok(values.len())
+}
+
+/// Compresses and decompresses fixed-size blocks of integer values.
+///
+/// `T` is the [`Element`] type of the uncompressed values (`u32` by default).
+/// The compressed stream is always a sequence of `u32` words.
 ///
 /// The associated type [`Block`](BlockCodec::Block) is the concrete fixed-size
 /// array, e.g. `[u32; 256]`. Using an associated *type* (not an associated
@@ -35,14 +119,14 @@ pub(crate) fn default_max_decoded_len(compressed_words: usize) -> usize {
 ///         out: &mut Vec<u32>) -> FastPForResult<usize> { todo!() }
 /// }
 /// ```
-pub trait BlockCodec: Default {
+pub trait BlockCodec<T: Element = u32>: Default {
     /// The fixed-size block type.  Must be plain-old-data (`Pod`).
-    /// In practice this will be `[u32; 128]` or `[u32; 256]`.
+    /// In practice this will be `[T; 128]` or `[T; 256]`.
     type Block: Pod;
 
-    /// Number of `u32` elements in one block.
+    /// Number of `T` elements in one block.
     ///
-    /// Equal to `size_of::<Self::Block>() / 4`. Use this when computing
+    /// Equal to `size_of::<Self::Block>() / size_of::<T>()`. Use this when computing
     /// element counts from block counts, e.g. `n_blocks * codec.elements_per_block()`.
     #[inline]
     #[must_use]
@@ -50,7 +134,7 @@ pub trait BlockCodec: Default {
     where
         Self: Sized,
     {
-        size_of::<Self::Block>() / size_of::<u32>()
+        size_of::<Self::Block>() / size_of::<T>()
     }
 
     /// Compress a slice of complete, fixed-size blocks.
@@ -59,6 +143,43 @@ pub trait BlockCodec: Default {
     /// [`slice_to_blocks`] and handle any remainder separately.
     fn encode_blocks(&mut self, blocks: &[Self::Block], out: &mut Vec<u32>) -> FastPForResult<()>;
 
+    /// Worst-case number of `u32` words that [`encode_blocks`](BlockCodec::encode_blocks)
+    /// writes for `n_values` values, including the length header.
+    ///
+    /// The default is a generous estimate for codecs without a proven bound;
+    /// the Rust codecs override it with their exact worst case.
+    #[must_use]
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        default_max_encoded_len(n_values.saturating_mul(T::LANES))
+    }
+
+    /// Number of values the codec encodes together as one page, or `None` if
+    /// it has no pages.
+    #[must_use]
+    fn page_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Like [`encode_blocks`](BlockCodec::encode_blocks), writing into the start
+    /// of a caller-provided slice instead of appending to a `Vec`.
+    ///
+    /// Returns the number of words written to `out[..n]`. A slice of
+    /// [`max_compressed_len`](BlockCodec::max_compressed_len) words always
+    /// suffices; the Rust codecs return
+    /// [`OutputBufferTooSmall`](FastPForError::OutputBufferTooSmall) for anything
+    /// shorter, without writing.
+    ///
+    /// The default implementation encodes into a temporary `Vec` and copies.
+    fn encode_blocks_into(
+        &mut self,
+        blocks: &[Self::Block],
+        out: &mut [u32],
+    ) -> FastPForResult<usize> {
+        let mut words = Vec::new();
+        self.encode_blocks(blocks, &mut words)?;
+        copy_into(&words, out)
+    }
+
     /// Decompress blocks from `input`, using the length stored in the header.
     ///
     /// Returns the number of input `u32` words consumed, so the caller (e.g.
@@ -76,9 +197,31 @@ pub trait BlockCodec: Default {
         &mut self,
         input: &[u32],
         expected_len: Option<u32>,
-        out: &mut Vec<u32>,
+        out: &mut Vec<T>,
     ) -> FastPForResult<usize>;
 
+    /// Like [`decode_blocks`](BlockCodec::decode_blocks), writing into the start
+    /// of a caller-provided slice instead of appending to a `Vec`.
+    ///
+    /// Returns `(consumed, written)`: the number of input `u32` words consumed
+    /// and the number of values written to `out[..written]`. Returns
+    /// [`OutputBufferTooSmall`](FastPForError::OutputBufferTooSmall) if `out`
+    /// cannot hold every decoded value.
+    ///
+    /// The default implementation decodes into a temporary `Vec` and copies;
+    /// the Rust codecs override it to decode in place.
+    fn decode_blocks_into(
+        &mut self,
+        input: &[u32],
+        expected_len: Option<u32>,
+        out: &mut [T],
+    ) -> FastPForResult<(usize, usize)> {
+        let mut values = Vec::new();
+        let consumed = self.decode_blocks(input, expected_len, &mut values)?;
+        copy_into(&values, out)?;
+        Ok((consumed, values.len()))
+    }
+
     /// Maximum decompressed element count for a given compressed input length.
     /// Reject `expected_len` values exceeding this to avoid allocation from bad data.
     #[inline]
@@ -91,31 +234,61 @@ pub trait BlockCodec: Default {
     }
 }
 
-/// Codec that supports compressing 64-bit integers into a 32-bit word stream.
-///
-/// Only three C++ codecs implement this trait: `CppFastPFor128`,
-/// `CppFastPFor256`, and `CppVarInt`. For simple use, call
-/// `encode64` / `decode64` directly on the struct — no trait import required.
-///
-/// Import `BlockCodec64` only when writing generic code over multiple codecs
-/// that support 64-bit compression.
-#[cfg(feature = "cpp")]
-pub trait BlockCodec64 {
-    /// Compress 64-bit integers into a 32-bit word stream.
-    fn encode64(&mut self, input: &[u64], out: &mut Vec<u32>) -> FastPForResult<()>;
-    /// Decompress 64-bit integers from a 32-bit word stream.
-    fn decode64(&mut self, input: &[u32], out: &mut Vec<u64>) -> FastPForResult<()>;
-}
-
-/// Compresses and decompresses an arbitrary-length `&[u32]` slice.
+/// Compresses and decompresses an arbitrary-length `&[T]` slice.
 ///
 /// Handles any input length including sub-block remainders.  All pure
 /// variable-length codecs (e.g. `VariableByte`, `JustCopy`) implement this
 /// trait directly.  Block-oriented codecs are wrapped in `CompositeCodec`
 /// to produce an `AnyLenCodec`.
-pub trait AnyLenCodec: Default {
-    /// Compress an arbitrary-length slice of `u32` values.
-    fn encode(&mut self, input: &[u32], out: &mut Vec<u32>) -> FastPForResult<()>;
+///
+/// `T` is the [`Element`] type of the uncompressed values (`u32` by default).
+/// Narrower types are decoded straight into `Vec<T>`; a decoded value that
+/// does not fit in `T` is reported as
+/// [`ValueOutOfRange`](crate::FastPForError::ValueOutOfRange).
+pub trait AnyLenCodec<T: Element = u32>: Default {
+    /// Compress an arbitrary-length slice of `T` values.
+    fn encode(&mut self, input: &[T], out: &mut Vec<u32>) -> FastPForResult<()>;
+
+    /// Worst-case number of `u32` words that [`encode`](AnyLenCodec::encode)
+    /// writes for `n_values` values.
+    ///
+    /// Use it to size output regions for [`encode_into`](AnyLenCodec::encode_into).
+    /// The default is a generous estimate for codecs without a proven bound
+    /// (such as the C++ codecs); the Rust codecs override it with their exact
+    /// worst case.
+    #[must_use]
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        default_max_encoded_len(n_values.saturating_mul(T::LANES))
+    }
+
+    /// Number of values the codec encodes together as one page, or `None` if
+    /// it has no pages.
+    ///
+    /// Splitting the input into multiples of it, as
+    /// [`StreamEncoder`](crate::StreamEncoder) does by default, produces only
+    /// full pages. The default is `None`; the `FastPFor` composites return
+    /// their page size in values.
+    #[must_use]
+    fn page_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// Like [`encode`](AnyLenCodec::encode), writing into the start of a
+    /// caller-provided slice instead of appending to a `Vec`.
+    ///
+    /// Returns the number of words written to `out[..n]`. A slice of
+    /// [`max_compressed_len`](AnyLenCodec::max_compressed_len)`(input.len())`
+    /// words always suffices; the Rust codecs return
+    /// [`OutputBufferTooSmall`](FastPForError::OutputBufferTooSmall) for anything
+    /// shorter, without writing.
+    ///
+    /// The default implementation encodes into a temporary `Vec` and copies;
+    /// the Rust codecs override it to encode in place.
+    fn encode_into(&mut self, input: &[T], out: &mut [u32]) -> FastPForResult<usize> {
+        let mut words = Vec::new();
+        self.encode(input, &mut words)?;
+        copy_into(&words, out)
+    }
 
     /// Maximum decompressed element count for a given compressed input length.
     /// Reject `expected_len` values exceeding this to avoid allocation from bad data.
@@ -128,7 +301,7 @@ pub trait AnyLenCodec: Default {
         default_max_decoded_len(compressed_words)
     }
 
-    /// Decompress a previously compressed slice of `u32` values.
+    /// Decompress a previously compressed slice of `T` values.
     ///
     /// When `expected_len` is `Some(n)`:
     /// - Rejects if `n` exceeds [`max_decompressed_len`](AnyLenCodec::max_decompressed_len)(`input.len()`)
@@ -140,15 +313,165 @@ pub trait AnyLenCodec: Default {
     fn decode(
         &mut self,
         input: &[u32],
-        out: &mut Vec<u32>,
+        out: &mut Vec<T>,
+        expected_len: Option<u32>,
+    ) -> FastPForResult<()>;
+
+    /// Like [`decode`](AnyLenCodec::decode), writing into the start of a
+    /// caller-provided slice instead of appending to a `Vec`.
+    ///
+    /// Returns the number of values written to `out[..n]`; the rest of `out` is
+    /// left untouched. Returns
+    /// [`OutputBufferTooSmall`](FastPForError::OutputBufferTooSmall) if `out`
+    /// cannot hold every decoded value, so a buffer of the known value count
+    /// doubles as the length check.
+    ///
+    /// The default implementation decodes into a temporary `Vec` and copies;
+    /// the Rust codecs override it to decode in place without allocating.
+    fn decode_into(&mut self, input: &[u32], out: &mut [T]) -> FastPForResult<usize> {
+        let mut values = Vec::new();
+        self.decode(input, &mut values, None)?;
+        copy_into(&values, out)
+    }
+
+    /// Like [`encode`](AnyLenCodec::encode), appending the compressed words to
+    /// `out` as little-endian bytes.
+    fn encode_to_bytes(&mut self, input: &[T], out: &mut Vec<u8>) -> FastPForResult<()> {
+        let mut words = Vec::new();
+        self.encode(input, &mut words)?;
+        extend_le_bytes(&words, out);
+        Ok(())
+    }
+
+    /// Like [`decode`](AnyLenCodec::decode), reading the compressed words from
+    /// little-endian bytes.
+    ///
+    /// `input` may have any alignment. Its length must be a multiple of 4, or
+    /// [`InvalidInputLength`](FastPForError::InvalidInputLength) is returned.
+    fn decode_from_bytes(
+        &mut self,
+        input: &[u8],
+        out: &mut Vec<T>,
+        expected_len: Option<u32>,
+    ) -> FastPForResult<()> {
+        with_le_words(input, |words| self.decode(words, out, expected_len))
+    }
+}
+
+/// Object-safe counterpart of [`AnyLenCodec`], for choosing a codec at runtime.
+///
+/// `AnyLenCodec` requires `Default` and has associated functions, so it cannot
+/// be used as `dyn AnyLenCodec`. Every `AnyLenCodec<T>` (Rust or C++) implements
+/// `DynCodec<T>` automatically, so any codec can be stored as `Box<dyn DynCodec>`.
+///
+/// The methods carry a `_dyn` suffix so that they never clash with the
+/// [`AnyLenCodec`] methods when both traits are in scope.
+///
+/// # Example
+///
+/// ```
+/// use fastpfor::{DynCodec, FastPFor128, FastPFor256, VariableByte};
+///
+/// let codec_name = "vbyte";
+/// let mut codec: Box<dyn DynCodec> = match codec_name {
+///     "fastpfor128" => Box::new(FastPFor128::default()),
+///     "vbyte" => Box::new(VariableByte::new()),
+///     _ => Box::new(FastPFor256::default()),
+/// };
+///
+/// let input: Vec<u32> = (0..100).collect();
+/// let mut encoded = Vec::new();
+/// codec.encode_dyn(&input, &mut encoded).unwrap();
+///
+/// let mut decoded = Vec::new();
+/// codec.decode_dyn(&encoded, &mut decoded, None).unwrap();
+/// assert_eq!(decoded, input);
+/// ```
+pub trait DynCodec<T: Element = u32> {
+    /// Compress an arbitrary-length slice of `T` values. See [`AnyLenCodec::encode`].
+    fn encode_dyn(&mut self, input: &[T], out: &mut Vec<u32>) -> FastPForResult<()>;
+
+    /// Decompress a previously compressed slice of `T` values. See [`AnyLenCodec::decode`].
+    fn decode_dyn(
+        &mut self,
+        input: &[u32],
+        out: &mut Vec<T>,
+        expected_len: Option<u32>,
+    ) -> FastPForResult<()>;
+
+    /// Worst-case compressed length. See [`AnyLenCodec::max_compressed_len`].
+    fn max_compressed_len_dyn(&self, n_values: usize) -> usize;
+
+    /// Compress into a caller-provided slice. See [`AnyLenCodec::encode_into`].
+    fn encode_into_dyn(&mut self, input: &[T], out: &mut [u32]) -> FastPForResult<usize>;
+
+    /// Decompress into a caller-provided slice. See [`AnyLenCodec::decode_into`].
+    fn decode_into_dyn(&mut self, input: &[u32], out: &mut [T]) -> FastPForResult<usize>;
+
+    /// See [`AnyLenCodec::encode_to_bytes`].
+    fn encode_to_bytes_dyn(&mut self, input: &[T], out: &mut Vec<u8>) -> FastPForResult<()>;
+
+    /// See [`AnyLenCodec::decode_from_bytes`].
+    fn decode_from_bytes_dyn(
+        &mut self,
+        input: &[u8],
+        out: &mut Vec<T>,
         expected_len: Option<u32>,
     ) -> FastPForResult<()>;
 }
 
+impl<T: Element, C: AnyLenCodec<T>> DynCodec<T> for C {
+    #[inline]
+    fn encode_dyn(&mut self, input: &[T], out: &mut Vec<u32>) -> FastPForResult<()> {
+        self.encode(input, out)
+    }
+
+    #[inline]
+    fn decode_dyn(
+        &mut self,
+        input: &[u32],
+        out: &mut Vec<T>,
+        expected_len: Option<u32>,
+    ) -> FastPForResult<()> {
+        self.decode(input, out, expected_len)
+    }
+
+    #[inline]
+    fn max_compressed_len_dyn(&self, n_values: usize) -> usize {
+        self.max_compressed_len(n_values)
+    }
+
+    #[inline]
+    fn encode_into_dyn(&mut self, input: &[T], out: &mut [u32]) -> FastPForResult<usize> {
+        self.encode_into(input, out)
+    }
+
+    #[inline]
+    fn decode_into_dyn(&mut self, input: &[u32], out: &mut [T]) -> FastPForResult<usize> {
+        self.decode_into(input, out)
+    }
+
+    #[inline]
+    fn encode_to_bytes_dyn(&mut self, input: &[T], out: &mut Vec<u8>) -> FastPForResult<()> {
+        self.encode_to_bytes(input, out)
+    }
+
+    #[inline]
+    fn decode_from_bytes_dyn(
+        &mut self,
+        input: &[u8],
+        out: &mut Vec<T>,
+        expected_len: Option<u32>,
+    ) -> FastPForResult<()> {
+        self.decode_from_bytes(input, out, expected_len)
+    }
+}
+
 /// Split a flat `&[u32]` into `(&[Blocks::Block], &[u32])` without copying.
 ///
-/// Uses `size_of::<Blocks::Block>() / 4` to determine the block
-/// size, then [`cast_slice`] for the zero-copy reinterpretation.
+/// Uses [`BlockCodec::size`] to determine the block size, then [`cast_slice`]
+/// for the zero-copy reinterpretation. For other element types use
+/// [`slice_to_blocks_of`].
 ///
 /// The first return value is the largest aligned prefix; the second is the
 /// sub-block remainder (`0..block_size - 1` values) that the caller must
@@ -165,8 +488,29 @@ pub trait AnyLenCodec: Default {
 /// ```
 #[must_use]
 pub fn slice_to_blocks<Blocks: BlockCodec + Sized>(input: &[u32]) -> (&[Blocks::Block], &[u32]) {
-    let block_u32s = Blocks::size();
-    let aligned_down = (input.len() / block_u32s) * block_u32s;
+    slice_to_blocks_of::<Blocks, u32>(input)
+}
+
+/// [`slice_to_blocks`] for any [`Element`] type `T`.
+///
+/// `T` is usually inferred from the input, so call it as
+/// `slice_to_blocks_of::<Codec, _>(&data)`.
+///
+/// # Example
+///
+/// ```
+/// # use fastpfor::{slice_to_blocks_of, FastPFor};
+/// let data: Vec<u64> = (0..300).collect();
+/// let (blocks, remainder) = slice_to_blocks_of::<FastPFor<128, u64>, _>(&data);
+/// assert_eq!(blocks.len(), 2);    // 2 blocks of [u64; 128]
+/// assert_eq!(remainder.len(), 44);
+/// ```
+#[must_use]
+pub fn slice_to_blocks_of<Blocks: BlockCodec<T>, T: Element>(
+    input: &[T],
+) -> (&[Blocks::Block], &[T]) {
+    let block_len = Blocks::size();
+    let aligned_down = (input.len() / block_len) * block_len;
     let (aligned, remainder) = input.split_at(aligned_down);
     let blocks: &[Blocks::Block] = cast_slice(aligned); // must not panic
     (blocks, remainder)
@@ -1,7 +1,7 @@
 use cxx::UniquePtr;
 
 use crate::FastPForResult;
-use crate::codec::{AnyLenCodec, BlockCodec64};
+use crate::codec::AnyLenCodec;
 use crate::cpp::ffi;
 use crate::cpp::wrappers::{
     decode32_anylen_ffi, decode64_to_vec_ffi, encode32_to_vec_ffi, encode64_to_vec_ffi,
@@ -9,8 +9,8 @@ use crate::cpp::wrappers::{
 
 // ── Codec struct definitions ─────────────────────────────────────────────────
 //
-// Single macro: all C++ codecs implement AnyLenCodec. Codecs marked with `@ 64`
-// also implement BlockCodec64 for 64-bit integer support.
+// Single macro: all C++ codecs implement AnyLenCodec. Codecs listed in
+// `implement_cpp_codecs_64!` also implement AnyLenCodec<u64> through `Cpp64`.
 
 /// Macro for C++ codec wrappers: struct + Default + [`AnyLenCodec`].
 macro_rules! implement_cpp_codecs {
@@ -58,7 +58,7 @@ macro_rules! implement_cpp_codecs {
             $(
                 #[test]
                 fn $name() {
-                    let _codec = $crate::cpp::$name::default();
+                    let _codec = <$crate::cpp::$name>::default();
                 }
             )*
         }
@@ -190,16 +190,213 @@ implement_cpp_codecs! {
     // CppVsEncoding => vsencoding_codec,  // This is leaking memory
 }
 
-/// Adds `BlockCodec64` impl for codecs that support 64-bit integers.
+// ── Thread safety ────────────────────────────────────────────────────────────
+//
+// `UniquePtr<IntegerCODEC>` is neither `Send` nor `Sync`, so each codec opts in
+// here after an audit of the C++ class it wraps. Every wrapper owns its C++
+// object outright and only calls into it from `&mut self` methods, so moving it
+// to another thread is sound as long as the object has no thread affinity,
+// which none of them has. `Sync` is only claimed for classes with no data
+// members at all; codecs that keep working buffers between calls stay `!Sync`.
+//
+// `CppSimdGroupSimple`, `CppSimdGroupSimpleRingBuf`, `CppSimple8bRle`,
+// `CppSimple9Rle` and `CppVarIntGb` have not been audited for thread-affine or
+// static state, so they stay `!Send` and cannot be shared through a
+// `CodecPool`.
+
+// SAFETY: `BP32` has no data members; it packs straight from input to output.
+unsafe impl Send for CppBP32 {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppBP32 {}
+
+// SAFETY: `JustCopy` has no data members.
+unsafe impl Send for CppCopy {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppCopy {}
+
+// SAFETY: `FastBinaryPacking<8>` has no data members.
+unsafe impl Send for CppFastBinaryPacking8 {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppFastBinaryPacking8 {}
+
+// SAFETY: `FastBinaryPacking<16>` has no data members.
+unsafe impl Send for CppFastBinaryPacking16 {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppFastBinaryPacking16 {}
+
+// SAFETY: `FastBinaryPacking<32>` has no data members.
+unsafe impl Send for CppFastBinaryPacking32 {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppFastBinaryPacking32 {}
+
+// SAFETY: `FastPFor<4>` owns its `datatobepacked` exception vectors and its
+// `bytescontainer` metadata buffer, which every call rewrites. They are only
+// touched through `&mut self`. Not `Sync`.
+unsafe impl Send for CppFastPFor128 {}
+
+// SAFETY: `FastPFor<8>` owns `datatobepacked` and `bytescontainer`, as above.
+// Not `Sync`.
+unsafe impl Send for CppFastPFor256 {}
+
+// SAFETY: `MaskedVByte` has no data members.
+unsafe impl Send for CppMaskedVByte {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppMaskedVByte {}
+
+// SAFETY: `NewPFor` owns the `exceptionsPositions`, `exceptionsValues`,
+// `exceptions` and `tobecoded` vectors, which every call rewrites. Not `Sync`.
+unsafe impl Send for CppNewPFor {}
+
+// SAFETY: `OptPFor` inherits the `NewPFor` vectors above. Not `Sync`.
+unsafe impl Send for CppOptPFor {}
+
+// SAFETY: `PFor2008` inherits the `PFor` `codedcopy` and `miss` vectors, which
+// every call rewrites. Not `Sync`.
+unsafe impl Send for CppPFor2008 {}
+
+// SAFETY: `PFor` owns the `codedcopy` and `miss` vectors, which every call
+// rewrites. Not `Sync`.
+unsafe impl Send for CppPFor {}
+
+// SAFETY: `SIMDBinaryPacking` has no data members.
+unsafe impl Send for CppSimdBinaryPacking {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppSimdBinaryPacking {}
+
+// SAFETY: `SIMDFastPFor<4>` owns `datatobepacked` and `bytescontainer` like
+// `FastPFor`. Not `Sync`.
+unsafe impl Send for CppSimdFastPFor128 {}
+
+// SAFETY: `SIMDFastPFor<8>` owns `datatobepacked` and `bytescontainer` like
+// `FastPFor`. Not `Sync`.
+unsafe impl Send for CppSimdFastPFor256 {}
+
+// SAFETY: `SIMDNewPFor` owns the `NewPFor` vectors. Not `Sync`.
+unsafe impl Send for CppSimdNewPFor {}
+
+// SAFETY: `SIMDOptPFor` owns the `NewPFor` vectors. Not `Sync`.
+unsafe impl Send for CppSimdOptPFor {}
+
+// SAFETY: `SIMDPFor` owns the `PFor` `codedcopy` and `miss` vectors. Not `Sync`.
+unsafe impl Send for CppSimdPFor {}
+
+// SAFETY: `SIMDSimplePFor` owns `datatobepacked` and `bytescontainer` like
+// `SimplePFor`. Not `Sync`.
+unsafe impl Send for CppSimdSimplePFor {}
+
+// SAFETY: `Simple16` has no data members.
+unsafe impl Send for CppSimple16 {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppSimple16 {}
+
+// SAFETY: `Simple8b` has no data members.
+unsafe impl Send for CppSimple8b {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppSimple8b {}
+
+// SAFETY: `Simple9` has no data members.
+unsafe impl Send for CppSimple9 {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppSimple9 {}
+
+// SAFETY: `SimplePFor` owns the `datatobepacked` exception vectors and the
+// `bytescontainer` metadata buffer, which every call rewrites. Not `Sync`.
+unsafe impl Send for CppSimplePFor {}
+
+// SAFETY: `StreamVByte` has no data members.
+unsafe impl Send for CppStreamVByte {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppStreamVByte {}
+
+// SAFETY: `VariableByte` has no data members.
+unsafe impl Send for CppVByte {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppVByte {}
+
+// SAFETY: `VarInt` has no data members.
+unsafe impl Send for CppVarInt {}
+// SAFETY: as above, there is no C++ state to share.
+unsafe impl Sync for CppVarInt {}
+
+/// A 64-bit C++ codec used through [`AnyLenCodec<u64>`].
+///
+/// `CppFastPFor128`, `CppFastPFor256` and `CppVarInt` can also compress `u64`
+/// values. They implement `AnyLenCodec<u32>` only, so that decoding with them
+/// never needs a type annotation; wrap one in `Cpp64` to get the `u64` API.
+///
+/// ```
+/// use fastpfor::AnyLenCodec;
+/// use fastpfor::cpp::{Cpp64, CppFastPFor256};
+///
+/// let mut codec = Cpp64::<CppFastPFor256>::default();
+/// let input: Vec<u64> = (0..300).map(|i| i << 40).collect();
+/// let mut encoded = Vec::new();
+/// codec.encode(&input, &mut encoded).unwrap();
+/// let mut decoded = Vec::new();
+/// codec.decode(&encoded, &mut decoded, None).unwrap();
+/// assert_eq!(decoded, input);
+/// ```
+#[derive(Default)]
+pub struct Cpp64<C>(C);
+
+impl<C> Cpp64<C> {
+    /// Wraps `codec` for use with `u64` values.
+    #[must_use]
+    pub fn new(codec: C) -> Self {
+        Self(codec)
+    }
+
+    /// Returns the wrapped codec.
+    #[must_use]
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+/// Codec that supports compressing 64-bit integers into a 32-bit word stream.
+///
+/// Only three C++ codecs implement this trait: `CppFastPFor128`,
+/// `CppFastPFor256`, and `CppVarInt`. For simple use, call
+/// `encode64` / `decode64` directly on the struct — no trait import required.
+///
+/// Import `BlockCodec64` only when writing generic code over multiple codecs
+/// that support 64-bit compression.
+#[deprecated(note = "wrap the codec in `Cpp64` and use `AnyLenCodec<u64>`")]
+pub trait BlockCodec64 {
+    /// Compress 64-bit integers into a 32-bit word stream.
+    fn encode64(&mut self, input: &[u64], out: &mut Vec<u32>) -> FastPForResult<()>;
+    /// Decompress 64-bit integers from a 32-bit word stream.
+    fn decode64(&mut self, input: &[u32], out: &mut Vec<u64>) -> FastPForResult<()>;
+}
+
+/// Adds `AnyLenCodec<u64>` for `Cpp64<C>` and the deprecated `BlockCodec64`
+/// for codecs that support 64-bit integers.
 macro_rules! implement_cpp_codecs_64 {
-    ($($name:ident => $ffi:ident ,)*) => {
+    ($($name:ident,)*) => {
         $(
+            impl AnyLenCodec<u64> for Cpp64<$name> {
+                fn encode(&mut self, input: &[u64], out: &mut Vec<u32>) -> FastPForResult<()> {
+                    encode64_to_vec_ffi(&self.0.0, input, out)
+                }
+
+                fn decode(
+                    &mut self,
+                    input: &[u32],
+                    out: &mut Vec<u64>,
+                    expected_len: Option<u32>,
+                ) -> FastPForResult<()> {
+                    decode64_to_vec_ffi(&self.0.0, input, out, expected_len)
+                }
+            }
+
+            #[allow(deprecated)]
             impl BlockCodec64 for $name {
                 fn encode64(&mut self, input: &[u64], out: &mut Vec<u32>) -> FastPForResult<()> {
                     encode64_to_vec_ffi(&self.0, input, out)
                 }
+
                 fn decode64(&mut self, input: &[u32], out: &mut Vec<u64>) -> FastPForResult<()> {
-                    decode64_to_vec_ffi(&self.0, input, out)
+                    decode64_to_vec_ffi(&self.0, input, out, None)
                 }
             }
         )*
@@ -207,15 +404,15 @@ macro_rules! implement_cpp_codecs_64 {
 }
 
 implement_cpp_codecs_64! {
-    CppFastPFor128 => fastpfor128_codec,
-    CppFastPFor256 => fastpfor256_codec,
-    CppVarInt => varint_codec,
+    CppFastPFor128,
+    CppFastPFor256,
+    CppVarInt,
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
-    use crate::cpp::codecs::{CppFastPFor128, CppFastPFor256, CppVByte, CppVarInt};
-    use crate::test_utils::{decompress, decompress64, roundtrip, roundtrip64};
+    use crate::cpp::codecs::{Cpp64, CppFastPFor128, CppFastPFor256, CppVByte, CppVarInt};
+    use crate::test_utils::{decompress, decompress_elements, roundtrip, roundtrip_elements};
 
     /// C++ `fastpfor256_codec` returns `CompositeCodec<FastPFor<8>, VariableByte>` — already
     /// any-length. Use it directly; do not wrap in Rust `CompositeCodec`.
@@ -241,12 +438,79 @@ pub(crate) mod tests {
     #[test]
     fn test_fastpfor256_u64() {
         let input: Vec<u64> = (0..256).collect();
-        roundtrip64::<CppFastPFor256>(&input);
+        roundtrip_elements::<u64, Cpp64<CppFastPFor256>>(&input);
     }
 
     #[test]
     fn test_varint_u64() {
-        roundtrip64::<CppVarInt>(&[1u64, 2, 3, 4, 5]);
+        roundtrip_elements::<u64, Cpp64<CppVarInt>>(&[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_block_codec64_shims() {
+        use crate::cpp::codecs::BlockCodec64;
+
+        let input: Vec<u64> = (0..300).map(|i| i << 33).collect();
+        let mut codec = CppFastPFor128::default();
+        let mut encoded = Vec::new();
+        codec.encode64(&input, &mut encoded).unwrap();
+        let mut decoded = Vec::new();
+        codec.decode64(&encoded, &mut decoded).unwrap();
+        assert_eq!(decoded, input);
+        assert_eq!(
+            decompress_elements::<u64, Cpp64<CppFastPFor128>>(&encoded, None).unwrap(),
+            input
+        );
+    }
+
+    #[test]
+    fn test_audited_codecs_are_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<crate::cpp::CppBP32>();
+        assert_send::<crate::cpp::CppCopy>();
+        assert_send::<crate::cpp::CppFastBinaryPacking8>();
+        assert_send::<crate::cpp::CppFastBinaryPacking16>();
+        assert_send::<crate::cpp::CppFastBinaryPacking32>();
+        assert_send::<crate::cpp::CppFastPFor128>();
+        assert_send::<crate::cpp::CppFastPFor256>();
+        assert_send::<crate::cpp::CppMaskedVByte>();
+        assert_send::<crate::cpp::CppNewPFor>();
+        assert_send::<crate::cpp::CppOptPFor>();
+        assert_send::<crate::cpp::CppPFor2008>();
+        assert_send::<crate::cpp::CppPFor>();
+        assert_send::<crate::cpp::CppSimdBinaryPacking>();
+        assert_send::<crate::cpp::CppSimdFastPFor128>();
+        assert_send::<crate::cpp::CppSimdFastPFor256>();
+        assert_send::<crate::cpp::CppSimdNewPFor>();
+        assert_send::<crate::cpp::CppSimdOptPFor>();
+        assert_send::<crate::cpp::CppSimdPFor>();
+        assert_send::<crate::cpp::CppSimdSimplePFor>();
+        assert_send::<crate::cpp::CppSimple16>();
+        assert_send::<crate::cpp::CppSimple8b>();
+        assert_send::<crate::cpp::CppSimple9>();
+        assert_send::<crate::cpp::CppSimplePFor>();
+        assert_send::<crate::cpp::CppStreamVByte>();
+        assert_send::<crate::cpp::CppVByte>();
+        assert_send::<crate::cpp::CppVarInt>();
+    }
+
+    #[test]
+    fn test_stateless_codecs_are_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<crate::cpp::CppBP32>();
+        assert_sync::<crate::cpp::CppCopy>();
+        assert_sync::<crate::cpp::CppFastBinaryPacking8>();
+        assert_sync::<crate::cpp::CppFastBinaryPacking16>();
+        assert_sync::<crate::cpp::CppFastBinaryPacking32>();
+        assert_sync::<crate::cpp::CppMaskedVByte>();
+        assert_sync::<crate::cpp::CppSimdBinaryPacking>();
+        assert_sync::<crate::cpp::CppSimple16>();
+        assert_sync::<crate::cpp::CppSimple8b>();
+        assert_sync::<crate::cpp::CppSimple9>();
+        assert_sync::<crate::cpp::CppStreamVByte>();
+        assert_sync::<CppVByte>();
+        assert_sync::<CppVarInt>();
     }
 
     #[test]
@@ -262,12 +526,20 @@ pub(crate) mod tests {
 
     #[test]
     fn test_decode64_empty_input() {
-        assert!(decompress64::<CppFastPFor256>(&[]).unwrap().is_empty());
+        assert!(
+            decompress_elements::<u64, Cpp64<CppFastPFor256>>(&[], None)
+                .unwrap()
+                .is_empty()
+        );
     }
 
     #[test]
     fn test_decode64_empty_format() {
-        assert!(decompress64::<CppVarInt>(&[]).unwrap().is_empty());
+        assert!(
+            decompress_elements::<u64, Cpp64<CppVarInt>>(&[], None)
+                .unwrap()
+                .is_empty()
+        );
     }
 
     #[test]
@@ -1,9 +1,17 @@
 //! C++ codec wrappers — see the [crate-level documentation](crate) for usage and codec selection.
 //!
 //! All C++ codecs are composite (any-length) and implement [`AnyLenCodec`] only.
-//! Codecs marked with `@ 64` also implement [`BlockCodec64`] for 64-bit integers.
+//! `CppFastPFor128`, `CppFastPFor256` and `CppVarInt` also implement `AnyLenCodec<u64>`
+//! when wrapped in [`Cpp64`].
 //!
-//! **Thread safety:** instances have internal state and are not thread-safe. Use one per thread.
+//! **Thread safety:** codecs are used by one thread at a time through `&mut self`. All
+//! but `CppSimdGroupSimple`, `CppSimdGroupSimpleRingBuf`, `CppSimple8bRle`,
+//! `CppSimple9Rle` and `CppVarIntGb`, whose C++ state has not been audited, are `Send`,
+//! so an instance can be moved to a worker thread. Only codecs whose C++ class has no
+//! data members (the binary packing, `Simple*`, byte-oriented and copy codecs) are also
+//! `Sync`; the `PFor` family and the `FastPFor` variants keep working buffers between
+//! calls and are not. Use a [`CodecPool`](crate::CodecPool) to share `Send` instances
+//! between threads.
 
 mod codecs;
 mod wrappers;
@@ -1,7 +1,7 @@
 use cxx::UniquePtr;
 
 use crate::FastPForResult;
-use crate::codec::default_max_decoded_len;
+use crate::codec::{default_max_decoded_len, default_max_encoded_len};
 use crate::cpp::ffi;
 use crate::helpers::AsUsize;
 
@@ -15,7 +15,8 @@ pub fn encode32_to_vec_ffi(
     input: &[u32],
     out: &mut Vec<u32>,
 ) -> FastPForResult<()> {
-    let capacity = input.len() * 2 + 1024;
+    // Matches `AnyLenCodec::max_compressed_len`, which C++ codecs do not override.
+    let capacity = default_max_encoded_len(input.len());
     let start = out.len();
     out.resize(start + capacity, 0);
     let n = ffi::codec_encode32(codec, input, &mut out[start..])?;
@@ -71,7 +72,8 @@ pub fn encode64_to_vec_ffi(
     input: &[u64],
     out: &mut Vec<u32>,
 ) -> FastPForResult<()> {
-    let capacity = input.len() * 3 + 1024;
+    // Matches `AnyLenCodec::<u64>::max_compressed_len`: two words per value.
+    let capacity = default_max_encoded_len(input.len() * 2);
     let start = out.len();
     out.resize(start + capacity, 0);
     let n = ffi::codec_encode64(codec, input, &mut out[start..])?;
@@ -83,14 +85,21 @@ pub fn decode64_to_vec_ffi(
     codec: &UniquePtr<ffi::IntegerCODEC>,
     input: &[u32],
     out: &mut Vec<u64>,
+    expected_len: Option<u32>,
 ) -> FastPForResult<()> {
+    if let Some(n) = expected_len {
+        n.is_valid_expected(default_max_decoded_len(input.len()))?;
+    }
+    let start = out.len();
     if !input.is_empty() {
         // C++ decodeArray needs output buffer. Variable-byte can pack multiple values per word.
         let capacity = input.len().saturating_mul(4);
-        let start = out.len();
         out.resize(start + capacity, 0);
         let n = ffi::codec_decode64(codec, input, &mut out[start..])?;
         out.truncate(start + n);
     }
+    if let Some(n) = expected_len {
+        (out.len() - start).is_decoded_mismatch(n)?;
+    }
     Ok(())
 }
@@ -0,0 +1,187 @@
+use core::fmt::Debug;
+
+use bytemuck::{Pod, cast_slice, cast_slice_mut};
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+    impl Sealed for u64 {}
+    impl Sealed for i32 {}
+    impl Sealed for i64 {}
+}
+
+/// Integer type that the codecs can compress and decompress.
+///
+/// All codecs pack values as `u32` words internally. Narrower types are widened
+/// on encode and narrowed straight into the caller's `Vec<T>` on decode, without
+/// materialising a full-length `Vec<u32>` in between. 64-bit types are split
+/// into two `u32` lanes (low half first), see [`LANES`](Element::LANES).
+///
+/// Signed types are stored by their two's-complement bit pattern, so small
+/// negative values cost as many bits as large unsigned ones. Zigzag-encode
+/// them first if that matters for your data.
+///
+/// This trait is sealed: it is implemented for `u8`, `u16`, `u32`, `u64`,
+/// `i32` and `i64` only.
+pub trait Element: Pod + Default + PartialEq + Debug + sealed::Sealed {
+    /// Number of `u32` words needed to hold one value: `1` for types up to
+    /// 32 bits, `2` for 64-bit types.
+    const LANES: usize;
+
+    /// Returns the value's bit pattern zero-extended to `u64`.
+    fn to_u64(self) -> u64;
+
+    /// Converts a decoded bit pattern back, returning `None` if it does not fit in `Self`.
+    fn from_u64(value: u64) -> Option<Self>;
+
+    /// Reinterprets `values` as `u32` words if `Self` is 32 bits wide.
+    ///
+    /// Codecs use this to skip the widening copy on the common `u32` path.
+    #[inline]
+    #[must_use]
+    fn as_words(values: &[Self]) -> Option<&[u32]> {
+        let _ = values;
+        None
+    }
+
+    /// Mutable counterpart of [`as_words`](Element::as_words).
+    #[inline]
+    #[must_use]
+    fn as_words_mut(values: &mut [Self]) -> Option<&mut [u32]> {
+        let _ = values;
+        None
+    }
+
+    /// Reinterprets `u32` words as values if `Self` is 32 bits wide.
+    ///
+    /// The inverse of [`as_words`](Element::as_words).
+    #[inline]
+    #[must_use]
+    fn from_words(words: &[u32]) -> Option<&[Self]> {
+        let _ = words;
+        None
+    }
+
+    /// Returns the `u32` word stored in the given lane (`0` = low half).
+    #[inline]
+    #[must_use]
+    fn lane(self, lane: usize) -> u32 {
+        (self.to_u64() >> (32 * lane)) as u32
+    }
+}
+
+impl Element for u8 {
+    const LANES: usize = 1;
+
+    #[inline]
+    fn to_u64(self) -> u64 {
+        u64::from(self)
+    }
+
+    #[inline]
+    fn from_u64(value: u64) -> Option<Self> {
+        Self::try_from(value).ok()
+    }
+}
+
+impl Element for u16 {
+    const LANES: usize = 1;
+
+    #[inline]
+    fn to_u64(self) -> u64 {
+        u64::from(self)
+    }
+
+    #[inline]
+    fn from_u64(value: u64) -> Option<Self> {
+        Self::try_from(value).ok()
+    }
+}
+
+impl Element for u32 {
+    const LANES: usize = 1;
+
+    #[inline]
+    fn to_u64(self) -> u64 {
+        u64::from(self)
+    }
+
+    #[inline]
+    fn from_u64(value: u64) -> Option<Self> {
+        Self::try_from(value).ok()
+    }
+
+    #[inline]
+    fn as_words(values: &[Self]) -> Option<&[u32]> {
+        Some(values)
+    }
+
+    #[inline]
+    fn as_words_mut(values: &mut [Self]) -> Option<&mut [u32]> {
+        Some(values)
+    }
+
+    #[inline]
+    fn from_words(words: &[u32]) -> Option<&[Self]> {
+        Some(words)
+    }
+}
+
+impl Element for i32 {
+    const LANES: usize = 1;
+
+    #[inline]
+    fn to_u64(self) -> u64 {
+        u64::from(self as u32)
+    }
+
+    #[inline]
+    fn from_u64(value: u64) -> Option<Self> {
+        u32::try_from(value).ok().map(|v| v as Self)
+    }
+
+    #[inline]
+    fn as_words(values: &[Self]) -> Option<&[u32]> {
+        Some(cast_slice(values))
+    }
+
+    #[inline]
+    fn as_words_mut(values: &mut [Self]) -> Option<&mut [u32]> {
+        Some(cast_slice_mut(values))
+    }
+
+    #[inline]
+    fn from_words(words: &[u32]) -> Option<&[Self]> {
+        Some(cast_slice(words))
+    }
+}
+
+impl Element for u64 {
+    const LANES: usize = 2;
+
+    #[inline]
+    fn to_u64(self) -> u64 {
+        self
+    }
+
+    #[inline]
+    fn from_u64(value: u64) -> Option<Self> {
+        Some(value)
+    }
+}
+
+impl Element for i64 {
+    const LANES: usize = 2;
+
+    #[inline]
+    fn to_u64(self) -> u64 {
+        self as u64
+    }
+
+    #[inline]
+    fn from_u64(value: u64) -> Option<Self> {
+        Some(value as Self)
+    }
+}
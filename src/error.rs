@@ -46,6 +46,10 @@ pub enum FastPForError {
         max: usize,
     },
 
+    /// A decoded value does not fit in the requested element type
+    #[error("Decoded value {0} does not fit in the target integer type")]
+    ValueOutOfRange(u64),
+
     /// Decoded element count did not match the expected count
     #[error("Decoded {actual} elements, expected {expected}")]
     DecodedCountMismatch {
@@ -54,4 +58,75 @@ pub enum FastPForError {
         /// Expected count provided by the caller
         expected: usize,
     },
+
+    /// Framed stream does not start with the expected magic bytes
+    #[error("Invalid frame magic {0:#010x}")]
+    InvalidFrameMagic(u32),
+
+    /// Framed stream uses a format version this build cannot read
+    #[error("Unsupported frame format version {0}")]
+    UnsupportedFrameVersion(u8),
+
+    /// Framed stream names a codec that is unknown or disabled in this build
+    #[error("Unknown or unavailable codec ID {0}")]
+    UnknownCodecId(u16),
+
+    /// Framed stream holds elements of a different width than requested
+    #[error("Frame holds {actual}-bit elements, expected {expected}-bit")]
+    ElementWidthMismatch {
+        /// Element width requested by the caller, in bits
+        expected: u8,
+        /// Element width recorded in the frame, in bits
+        actual: u8,
+    },
+
+    /// Frame element width that the codec cannot store, or that frames do not support
+    #[error("Codec ID {codec_id} cannot frame {bits}-bit elements")]
+    UnsupportedFrameElement {
+        /// Registry ID of the codec
+        codec_id: u16,
+        /// Element width in bits
+        bits: u8,
+    },
+
+    /// A stream frame claims more compressed words than its values can need
+    #[error("Frame of {values} values claims {words} words, more than the maximum {max}")]
+    FrameTooLarge {
+        /// Value count from the frame header
+        values: usize,
+        /// Word count from the frame header
+        words: usize,
+        /// `max_compressed_len` of the value count
+        max: usize,
+    },
+
+    /// A checksummed `FastPFor` page does not match its stored CRC-32C
+    #[error("Checksum mismatch in page {page}")]
+    ChecksumMismatch {
+        /// Index of the corrupted page in the stream
+        page: usize,
+    },
+
+    /// Requested value range does not lie within the stream
+    #[error("Range {start}..{end} is out of bounds for {len} values")]
+    RangeOutOfBounds {
+        /// Start of the requested range
+        start: usize,
+        /// End of the requested range
+        end: usize,
+        /// Number of values in the stream
+        len: usize,
+    },
+
+    /// A stream that does not end on a page boundary is followed by more pages
+    #[error("Input {input} ends in a partial page, so it can only be concatenated last")]
+    PartialPageConcat {
+        /// Index of the offending input
+        input: usize,
+    },
+
+    /// Error from the reader or writer of a streaming encoder or decoder
+    #[cfg(feature = "std")]
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
@@ -0,0 +1,429 @@
+//! Self-describing framed container: records which codec produced a stream so
+//! that it can be decoded without out-of-band configuration.
+//!
+//! A frame records only the codec ID and the element width, so it has three
+//! limits:
+//!
+//! - Elements are `u32` or `u64` (see [`FramedElement`]), and the header's
+//!   element width is `32` or `64`. `u8`, `u16`, `i32` and `i64` values, which
+//!   [`DynCodec`] also handles, must be converted before framing.
+//! - Only codecs with a [`registry`] ID can be framed. [`JavaFastPFor`],
+//!   [`SeekableFastPFor`], [`ChunkedCodec`] and [`SegmentedCodec`] have none,
+//!   so their streams cannot be framed.
+//! - The codec is always created by the [`registry`] with its default
+//!   configuration. Streams written with a custom [`FastPForConfig`] page size
+//!   or with page checksums cannot be framed.
+//!
+//! [`FastPForConfig`]: crate::FastPForConfig
+//! [`JavaFastPFor`]: crate::JavaFastPFor
+//! [`SeekableFastPFor`]: crate::SeekableFastPFor
+//! [`ChunkedCodec`]: crate::ChunkedCodec
+//! [`SegmentedCodec`]: crate::SegmentedCodec
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::helpers::AsUsize;
+use crate::registry::{self, CodecInfo};
+use crate::{DynCodec, Element, FastPForError, FastPForResult};
+
+/// First word of every frame: the bytes `FPFr` in little-endian order.
+const FRAME_MAGIC: u32 = u32::from_le_bytes(*b"FPFr");
+
+/// Current frame format version.
+const FRAME_VERSION: u8 = 1;
+
+/// Number of `u32` words in the frame header.
+const HEADER_WORDS: usize = 5;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for u32 {}
+    impl Sealed for u64 {}
+}
+
+/// Element types that can be stored in a frame: `u32` and `u64`.
+///
+/// This trait is sealed. The registry only creates codecs for these two widths,
+/// and not every codec handles `u64`; framing a `u64` slice with such a codec
+/// fails with [`FastPForError::UnsupportedFrameElement`].
+pub trait FramedElement: Element + sealed::Sealed {
+    /// Element width in bits, as recorded in the frame header.
+    const BITS: u8;
+
+    /// Creates the codec described by `info` for this element type, or `None`
+    /// if the codec does not support it.
+    fn create_codec(info: &CodecInfo) -> Option<Box<dyn DynCodec<Self>>>;
+}
+
+impl FramedElement for u32 {
+    const BITS: u8 = 32;
+
+    fn create_codec(info: &CodecInfo) -> Option<Box<dyn DynCodec<Self>>> {
+        Some(info.create())
+    }
+}
+
+impl FramedElement for u64 {
+    const BITS: u8 = 64;
+
+    fn create_codec(info: &CodecInfo) -> Option<Box<dyn DynCodec<Self>>> {
+        info.create_u64()
+    }
+}
+
+/// Parsed header of a framed stream.
+///
+/// # Wire format
+///
+/// ```text
+/// [ magic: "FPFr" ]
+/// [ version: u8 | element bits: u8 (32 or 64) | codec id: u16 ]   (low byte first)
+/// [ count: u64 (low word, high word) ]
+/// [ words: u32 ] [ encoded data... ]
+/// ```
+///
+/// `codec id` is the stable [`registry`] ID of the codec that produced the
+/// encoded data, and `words` is its length in `u32` words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameHeader {
+    version: u8,
+    element_bits: u8,
+    codec_id: u16,
+    count: u64,
+    encoded_len: u32,
+}
+
+impl FrameHeader {
+    /// Parses the header at the start of `input`.
+    ///
+    /// Checks the magic, the version and that the element width is `32` or
+    /// `64`; the codec ID is checked by [`decode_framed`].
+    pub fn parse(input: &[u32]) -> FastPForResult<Self> {
+        let Some(header) = input.first_chunk::<HEADER_WORDS>() else {
+            return Err(FastPForError::NotEnoughData);
+        };
+        if header[0] != FRAME_MAGIC {
+            return Err(FastPForError::InvalidFrameMagic(header[0]));
+        }
+        let [version, element_bits, ..] = header[1].to_le_bytes();
+        if version != FRAME_VERSION {
+            return Err(FastPForError::UnsupportedFrameVersion(version));
+        }
+        let codec_id = (header[1] >> 16) as u16;
+        if !matches!(element_bits, 32 | 64) {
+            return Err(FastPForError::UnsupportedFrameElement {
+                codec_id,
+                bits: element_bits,
+            });
+        }
+        Ok(Self {
+            version,
+            element_bits,
+            codec_id,
+            count: u64::from(header[2]) | (u64::from(header[3]) << 32),
+            encoded_len: header[4],
+        })
+    }
+
+    /// Frame format version.
+    #[must_use]
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Width of the framed elements in bits, `32` or `64`.
+    #[must_use]
+    pub fn element_bits(&self) -> u8 {
+        self.element_bits
+    }
+
+    /// Stable [`registry`] ID of the codec that produced the encoded data.
+    #[must_use]
+    pub fn codec_id(&self) -> u16 {
+        self.codec_id
+    }
+
+    /// Number of values in the frame.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Length of the encoded data in `u32` words, excluding the header.
+    #[must_use]
+    pub fn encoded_len(&self) -> u32 {
+        self.encoded_len
+    }
+
+    /// Total length of the frame in `u32` words, including the header.
+    #[must_use]
+    pub fn frame_len(&self) -> usize {
+        HEADER_WORDS + self.encoded_len.as_usize()
+    }
+
+    fn to_words(self) -> [u32; HEADER_WORDS] {
+        [
+            FRAME_MAGIC,
+            u32::from_le_bytes([self.version, self.element_bits, 0, 0])
+                | (u32::from(self.codec_id) << 16),
+            self.count as u32,
+            (self.count >> 32) as u32,
+            self.encoded_len,
+        ]
+    }
+}
+
+/// Compresses `input` with the codec described by `info` and appends it to
+/// `out` as a self-describing frame.
+///
+/// A frame records only the codec ID and the element width, so the codec is
+/// re-created from the [`registry`] with its default configuration. Streams
+/// written with a custom [`FastPForConfig`](crate::FastPForConfig) page size or
+/// with page checksums cannot be framed, elements must be `u32` or `u64`, and
+/// codecs without a registry ID, such as [`SeekableFastPFor`](crate::SeekableFastPFor),
+/// cannot be framed at all.
+///
+/// Returns [`FastPForError::UnsupportedFrameElement`] if the codec cannot
+/// compress `T` values (see [`CodecInfo::supports_u64`]).
+///
+/// # Example
+///
+/// ```
+/// use fastpfor::{decode_framed, encode_framed, registry};
+///
+/// let input: Vec<u32> = (0..300).collect();
+/// let mut framed = Vec::new();
+/// encode_framed(registry::info_by_name("fastpfor128").unwrap(), &input, &mut framed).unwrap();
+///
+/// // No need to know which codec was used.
+/// let mut decoded: Vec<u32> = Vec::new();
+/// let consumed = decode_framed(&framed, &mut decoded).unwrap();
+/// assert_eq!(consumed, framed.len());
+/// assert_eq!(decoded, input);
+/// ```
+pub fn encode_framed<T: FramedElement>(
+    info: &CodecInfo,
+    input: &[T],
+    out: &mut Vec<u32>,
+) -> FastPForResult<()> {
+    let mut codec = T::create_codec(info).ok_or(FastPForError::UnsupportedFrameElement {
+        codec_id: info.id(),
+        bits: T::BITS,
+    })?;
+    let header_pos = out.len();
+    out.extend([0; HEADER_WORDS]);
+    if let Err(err) = codec.encode_dyn(input, out) {
+        out.truncate(header_pos);
+        return Err(err);
+    }
+    let words = out.len() - header_pos - HEADER_WORDS;
+    let Ok(encoded_len) = u32::try_from(words) else {
+        out.truncate(header_pos);
+        return Err(FastPForError::InvalidInputLength(words));
+    };
+    let header = FrameHeader {
+        version: FRAME_VERSION,
+        element_bits: T::BITS,
+        codec_id: info.id(),
+        count: input.len() as u64,
+        encoded_len,
+    };
+    out[header_pos..header_pos + HEADER_WORDS].copy_from_slice(&header.to_words());
+    Ok(())
+}
+
+/// Decodes one frame from the start of `input`, appending its values to `out`.
+///
+/// The codec is picked from the frame's codec ID among the codecs available
+/// in this build. Returns the number of words consumed, so that consecutive
+/// frames can be decoded from one buffer.
+pub fn decode_framed<T: FramedElement>(input: &[u32], out: &mut Vec<T>) -> FastPForResult<usize> {
+    let header = FrameHeader::parse(input)?;
+    if header.element_bits != T::BITS {
+        return Err(FastPForError::ElementWidthMismatch {
+            expected: T::BITS,
+            actual: header.element_bits,
+        });
+    }
+    let Some(payload) = input.get(HEADER_WORDS..header.frame_len()) else {
+        return Err(FastPForError::NotEnoughData);
+    };
+    let info = registry::info_by_id(header.codec_id)
+        .ok_or(FastPForError::UnknownCodecId(header.codec_id))?;
+    let mut codec = T::create_codec(info).ok_or(FastPForError::UnsupportedFrameElement {
+        codec_id: header.codec_id,
+        bits: header.element_bits,
+    })?;
+
+    let start = out.len();
+    codec.decode_dyn(payload, out, u32::try_from(header.count).ok())?;
+    let actual = out.len() - start;
+    if actual as u64 != header.count {
+        return Err(FastPForError::DecodedCountMismatch {
+            actual,
+            expected: usize::try_from(header.count).unwrap_or(usize::MAX),
+        });
+    }
+    Ok(header.frame_len())
+}
+
+#[cfg(all(test, feature = "rust"))]
+mod tests {
+    use super::*;
+
+    fn info(name: &str) -> &'static CodecInfo {
+        registry::info_by_name(name).unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip_every_codec() {
+        let data: Vec<u32> = (0..1000u32).map(|i| i * 7 % 513).collect();
+        for info in registry::all() {
+            let mut framed = Vec::new();
+            encode_framed(info, &data, &mut framed).unwrap();
+            let header = FrameHeader::parse(&framed).unwrap();
+            assert_eq!(header.codec_id(), info.id());
+            assert_eq!(header.count(), 1000);
+            assert_eq!(header.frame_len(), framed.len());
+
+            let mut decoded: Vec<u32> = Vec::new();
+            assert_eq!(decode_framed(&framed, &mut decoded).unwrap(), framed.len());
+            assert_eq!(decoded, data, "{}", info.name());
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_u64() {
+        let data: Vec<u64> = (0..600u64).map(|i| i << 35 | i).collect();
+        let mut framed = Vec::new();
+        encode_framed(info("fastpfor256"), &data, &mut framed).unwrap();
+        assert_eq!(FrameHeader::parse(&framed).unwrap().element_bits(), 64);
+        let mut decoded: Vec<u64> = Vec::new();
+        decode_framed(&framed, &mut decoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_picks_block_size_from_frame() {
+        let data: Vec<u32> = (0..700).collect();
+        let mut framed = Vec::new();
+        encode_framed(info("fastpfor128"), &data, &mut framed).unwrap();
+        encode_framed(info("fastpfor256"), &data, &mut framed).unwrap();
+
+        let mut decoded: Vec<u32> = Vec::new();
+        let first = decode_framed(&framed, &mut decoded).unwrap();
+        let second = decode_framed(&framed[first..], &mut decoded).unwrap();
+        assert_eq!(first + second, framed.len());
+        assert_eq!(decoded[..700], data);
+        assert_eq!(decoded[700..], data);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let mut framed = Vec::new();
+        encode_framed::<u32>(info("variablebyte"), &[], &mut framed).unwrap();
+        let mut decoded: Vec<u32> = Vec::new();
+        decode_framed(&framed, &mut decoded).unwrap();
+        assert!(decoded.is_empty());
+    }
+
+    #[test]
+    fn test_bad_magic_errors() {
+        let mut framed = Vec::new();
+        encode_framed(info("fastpfor256"), &[1u32, 2, 3], &mut framed).unwrap();
+        framed[0] ^= 1;
+        assert!(matches!(
+            decode_framed::<u32>(&framed, &mut Vec::new()),
+            Err(FastPForError::InvalidFrameMagic(_))
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_version_errors() {
+        let mut framed = Vec::new();
+        encode_framed(info("fastpfor256"), &[1u32, 2, 3], &mut framed).unwrap();
+        framed[1] = (framed[1] & !0xFF) | 9;
+        assert!(matches!(
+            decode_framed::<u32>(&framed, &mut Vec::new()),
+            Err(FastPForError::UnsupportedFrameVersion(9))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_codec_errors() {
+        let mut framed = Vec::new();
+        encode_framed(info("fastpfor256"), &[1u32, 2, 3], &mut framed).unwrap();
+        framed[1] |= 0xFFFF << 16;
+        assert!(matches!(
+            decode_framed::<u32>(&framed, &mut Vec::new()),
+            Err(FastPForError::UnknownCodecId(u16::MAX))
+        ));
+    }
+
+    #[test]
+    fn test_element_width_mismatch_errors() {
+        let mut framed = Vec::new();
+        encode_framed(info("fastpfor256"), &[1u64, 2, 3], &mut framed).unwrap();
+        assert!(matches!(
+            decode_framed::<u32>(&framed, &mut Vec::new()),
+            Err(FastPForError::ElementWidthMismatch {
+                expected: 32,
+                actual: 64
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_element_width_errors() {
+        let mut framed = Vec::new();
+        encode_framed(info("fastpfor256"), &[1u32, 2, 3], &mut framed).unwrap();
+        framed[1] = (framed[1] & !0xFF00) | (16 << 8);
+        assert!(matches!(
+            FrameHeader::parse(&framed),
+            Err(FastPForError::UnsupportedFrameElement {
+                codec_id: 1,
+                bits: 16
+            })
+        ));
+        assert!(matches!(
+            decode_framed::<u32>(&framed, &mut Vec::new()),
+            Err(FastPForError::UnsupportedFrameElement {
+                codec_id: 1,
+                bits: 16
+            })
+        ));
+    }
+
+    #[cfg(feature = "cpp")]
+    #[test]
+    fn test_codec_without_u64_errors() {
+        let info = info("cpp_simple16");
+        assert!(!info.supports_u64());
+        assert!(matches!(
+            encode_framed(info, &[1u64, 2, 3], &mut Vec::new()),
+            Err(FastPForError::UnsupportedFrameElement { bits: 64, .. })
+        ));
+    }
+
+    #[test]
+    fn test_truncated_frame_errors() {
+        let data: Vec<u32> = (0..300).collect();
+        let mut framed = Vec::new();
+        encode_framed(info("fastpfor256"), &data, &mut framed).unwrap();
+        for len in [0, HEADER_WORDS - 1, framed.len() - 1] {
+            assert!(matches!(
+                decode_framed::<u32>(&framed[..len], &mut Vec::new()),
+                Err(FastPForError::NotEnoughData)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_count_mismatch_errors() {
+        let mut framed = Vec::new();
+        encode_framed(info("variablebyte"), &[1u32, 2, 3], &mut framed).unwrap();
+        framed[2] = 2;
+        assert!(decode_framed::<u32>(&framed, &mut Vec::new()).is_err());
+    }
+}
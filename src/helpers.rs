@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use crate::{FastPForError, FastPForResult};
 
 /// Finds the greatest multiple of `factor` that is less than or equal to `value`.
@@ -79,6 +81,13 @@ impl<T: Copy> GetWithErr<T> for &[T] {
     }
 }
 
+impl<T: Copy> GetWithErr<T> for &mut [T] {
+    #[inline]
+    fn get_val(&self, pos: impl AsUsize) -> FastPForResult<T> {
+        (&**self).get_val(pos)
+    }
+}
+
 impl<T: Copy> GetWithErr<T> for Vec<T> {
     #[inline]
     fn get_val(&self, pos: impl AsUsize) -> FastPForResult<T> {
@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(not(feature = "cpp"), forbid(unsafe_code))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
@@ -5,6 +6,8 @@
 #[cfg(not(any(feature = "cpp", feature = "rust")))]
 compile_error!("At least one of the features 'cpp' or 'rust' must be enabled");
 
+extern crate alloc;
+
 // Error types are always available regardless of which codec features are enabled.
 mod error;
 pub use error::{FastPForError, FastPForResult};
@@ -12,26 +15,49 @@ pub use error::{FastPForError, FastPForResult};
 #[cfg(feature = "cpp")]
 /// Rust wrapper for the [`FastPFOR` C++ library](https://github.com/fast-pack/FastPFor)
 pub mod cpp;
+#[cfg(feature = "cpp")]
+#[allow(deprecated)]
+pub use cpp::BlockCodec64;
 
 #[cfg(feature = "rust")]
 #[forbid(unsafe_code, reason = "Rust code must always be safe")]
 pub(crate) mod rust;
 
+mod chunked;
 mod codec;
-#[cfg(feature = "cpp")]
-pub use codec::BlockCodec64;
-pub use codec::{AnyLenCodec, BlockCodec, slice_to_blocks};
+mod element;
+mod framed;
+#[cfg(feature = "std")]
+mod pool;
+mod segmented;
+mod segments;
+#[cfg(feature = "std")]
+mod stream;
+pub use chunked::ChunkedCodec;
+pub use codec::{AnyLenCodec, BlockCodec, DynCodec, slice_to_blocks, slice_to_blocks_of};
+pub use element::Element;
+pub use framed::{FrameHeader, FramedElement, decode_framed, encode_framed};
+#[cfg(feature = "std")]
+pub use pool::{CodecPool, PooledCodec};
+pub use segmented::SegmentedCodec;
+#[cfg(feature = "std")]
+pub use stream::{StreamDecoder, StreamEncoder};
 
 pub(crate) mod helpers;
 
+pub mod registry;
+
 // Re-export bytemuck::Pod so that users writing generic `BlockCodec` code
 // can constrain their own `Block` associated-type bounds without a separate
 // `bytemuck` dependency.
 pub use bytemuck::Pod;
 #[cfg(feature = "rust")]
 pub use rust::{
-    CompositeCodec, FastPFor, FastPFor128, FastPFor256, FastPForBlock128, FastPForBlock256,
-    JustCopy, VariableByte,
+    Appender, BlockInfo, CompositeCodec, ExceptionWidth, FastPFor, FastPFor128,
+    FastPFor128Appender, FastPFor256, FastPFor256Appender, FastPForBlock128, FastPForBlock256,
+    FastPForConfig, FastPForIter, FastPForScratch, JavaFastPFor, JavaFastPFor128, JavaFastPFor256,
+    JavaVariableByte, JustCopy, PageInfo, SeekableFastPFor, SeekableFastPFor128,
+    SeekableFastPFor256, StreamInfo, VariableByte, VariableByteIter,
 };
 
 // `src/test_utils.rs` uses `fastpfor::...`; alias this crate for unit tests only.
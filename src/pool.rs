@@ -0,0 +1,203 @@
+//! [`CodecPool`]: shares codec instances between threads.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// A pool of codec instances for multi-threaded workloads.
+///
+/// Codecs keep scratch buffers between calls and take `&mut self`, so each
+/// thread needs its own instance. [`get`](Self::get) hands out an idle instance
+/// (or creates one with `C::default()`), and the returned [`PooledCodec`] puts
+/// it back when dropped. The pool therefore grows to the number of codecs in use
+/// at once, e.g. one per rayon worker or tokio task, and reuses them afterwards.
+///
+/// `CodecPool<C>` is `Sync` whenever `C` is `Send`, which holds for every Rust codec
+/// and all but five unaudited C++ codecs (see the `cpp` module), so a pool can be
+/// shared by reference or through an `Arc`.
+///
+/// # Example
+///
+/// ```
+/// use std::thread;
+///
+/// use fastpfor::{AnyLenCodec, CodecPool, FastPFor256};
+///
+/// let pool = CodecPool::<FastPFor256>::new();
+/// let columns: Vec<Vec<u32>> = (0..4).map(|i| (0..1000).map(|v| v * i).collect()).collect();
+///
+/// let pool = &pool;
+/// thread::scope(|s| {
+///     for column in &columns {
+///         s.spawn(move || {
+///             let mut codec = pool.get();
+///             let mut encoded = Vec::new();
+///             codec.encode(column, &mut encoded).unwrap();
+///
+///             let mut decoded = Vec::new();
+///             codec.decode(&encoded, &mut decoded, None).unwrap();
+///             assert_eq!(&decoded, column);
+///         });
+///     }
+/// });
+/// ```
+#[derive(Debug)]
+pub struct CodecPool<C> {
+    idle: Mutex<Vec<C>>,
+}
+
+impl<C> Default for CodecPool<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> CodecPool<C> {
+    /// Creates an empty pool. Instances are created on demand.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Takes an idle instance from the pool, or creates a new one if none is idle.
+    ///
+    /// The instance returns to the pool when the [`PooledCodec`] is dropped.
+    pub fn get(&self) -> PooledCodec<'_, C>
+    where
+        C: Default,
+    {
+        let codec = self.lock().pop().unwrap_or_default();
+        PooledCodec {
+            pool: self,
+            codec: Some(codec),
+        }
+    }
+
+    /// Number of instances currently waiting in the pool.
+    #[must_use]
+    pub fn idle_count(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// A panic while the lock is held cannot leave the `Vec` half-updated,
+    /// so a poisoned pool is still usable.
+    fn lock(&self) -> MutexGuard<'_, Vec<C>> {
+        self.idle.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A codec borrowed from a [`CodecPool`], returned to the pool on drop.
+///
+/// Dereferences to the codec, so trait methods such as
+/// [`AnyLenCodec::encode`](crate::AnyLenCodec::encode) can be called on it directly.
+#[derive(Debug)]
+pub struct PooledCodec<'a, C> {
+    pool: &'a CodecPool<C>,
+    /// Always `Some` until dropped.
+    codec: Option<C>,
+}
+
+impl<C> Deref for PooledCodec<'_, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.codec.as_ref().expect("codec is present until drop")
+    }
+}
+
+impl<C> DerefMut for PooledCodec<'_, C> {
+    fn deref_mut(&mut self) -> &mut C {
+        self.codec.as_mut().expect("codec is present until drop")
+    }
+}
+
+impl<C> Drop for PooledCodec<'_, C> {
+    fn drop(&mut self) {
+        if let Some(codec) = self.codec.take() {
+            self.pool.lock().push(codec);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::test_utils::{compress, decompress};
+    use crate::{AnyLenCodec as _, FastPFor128, VariableByte};
+
+    #[test]
+    fn test_pool_reuses_instances() {
+        let pool = CodecPool::<FastPFor128>::new();
+        assert_eq!(pool.idle_count(), 0);
+        {
+            let _a = pool.get();
+            let _b = pool.get();
+        }
+        assert_eq!(pool.idle_count(), 2);
+        let _c = pool.get();
+        assert_eq!(pool.idle_count(), 1);
+    }
+
+    #[test]
+    fn test_pool_across_threads() {
+        let pool = CodecPool::<FastPFor128>::default();
+        thread::scope(|s| {
+            for t in 0..4u32 {
+                let pool = &pool;
+                s.spawn(move || {
+                    for round in 0..8u32 {
+                        let data: Vec<u32> = (0..700).map(|i| i * t + round).collect();
+                        let mut codec = pool.get();
+                        let mut encoded = Vec::new();
+                        codec.encode(&data, &mut encoded).unwrap();
+                        assert_eq!(encoded, compress::<FastPFor128>(&data).unwrap());
+                        let mut decoded = Vec::new();
+                        codec.decode(&encoded, &mut decoded, None).unwrap();
+                        assert_eq!(decoded, data);
+                    }
+                });
+            }
+        });
+        assert!((1..=4).contains(&pool.idle_count()));
+    }
+
+    #[cfg(feature = "cpp")]
+    #[test]
+    fn test_pool_moves_cpp_codecs_across_threads() {
+        use crate::cpp::CppFastPFor256;
+
+        let pool = CodecPool::<CppFastPFor256>::new();
+        let data: Vec<u32> = (0..1000).collect();
+        let encoded = compress::<CppFastPFor256>(&data).unwrap();
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    let mut decoded: Vec<u32> = Vec::new();
+                    pool.get().decode(&encoded, &mut decoded, None).unwrap();
+                    assert_eq!(decoded, data);
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_pool_survives_panicking_user() {
+        let pool = CodecPool::<VariableByte>::new();
+        let result = thread::scope(|s| {
+            s.spawn(|| {
+                let _codec = pool.get();
+                let _guard = pool.lock();
+                panic!("poison the pool");
+            })
+            .join()
+        });
+        assert!(result.is_err());
+        let encoded = compress::<VariableByte>(&[1, 2, 3]).unwrap();
+        let mut decoded: Vec<u32> = Vec::new();
+        pool.get().decode(&encoded, &mut decoded, None).unwrap();
+        assert_eq!(decoded, decompress::<VariableByte>(&encoded, None).unwrap());
+    }
+}
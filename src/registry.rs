@@ -0,0 +1,292 @@
+//! Codec registry: stable names and numeric IDs for every available codec.
+//!
+//! Use it to persist which codec compressed a stream and re-create that codec
+//! later, in the spirit of the C++ `CODECFactory::getFromName`. Only codecs
+//! whose feature (`rust` or `cpp`) is enabled are listed.
+//!
+//! Names and IDs are part of the stable API: once assigned they are never
+//! reused or renumbered. Rust codecs use IDs below 100, and C++ codecs use IDs
+//! from 100 upwards, with the C++ factory name prefixed by `cpp_`.
+//!
+//! ```
+//! use fastpfor::registry;
+//!
+//! let info = registry::info_by_name("fastpfor256").unwrap();
+//! let mut codec = info.create();
+//!
+//! let input: Vec<u32> = (0..300).collect();
+//! let mut encoded = Vec::new();
+//! codec.encode_dyn(&input, &mut encoded).unwrap();
+//!
+//! // Later, from the persisted ID:
+//! let mut codec = registry::from_id(info.id()).unwrap();
+//! let mut decoded = Vec::new();
+//! codec.decode_dyn(&encoded, &mut decoded, None).unwrap();
+//! assert_eq!(decoded, input);
+//! ```
+
+use alloc::boxed::Box;
+
+use crate::DynCodec;
+
+/// Creates a fresh codec instance.
+pub type CodecFactory = fn() -> Box<dyn DynCodec>;
+
+/// Creates a fresh codec instance for `u64` values.
+pub type CodecFactory64 = fn() -> Box<dyn DynCodec<u64>>;
+
+/// A registered codec: its stable name, stable ID, and factories.
+#[derive(Clone, Copy, Debug)]
+pub struct CodecInfo {
+    name: &'static str,
+    id: u16,
+    factory: CodecFactory,
+    factory_u64: Option<CodecFactory64>,
+}
+
+impl CodecInfo {
+    const fn new(name: &'static str, id: u16, factory: CodecFactory) -> Self {
+        Self {
+            name,
+            id,
+            factory,
+            factory_u64: None,
+        }
+    }
+
+    const fn with_u64(mut self, factory_u64: CodecFactory64) -> Self {
+        self.factory_u64 = Some(factory_u64);
+        self
+    }
+
+    /// Stable lowercase name, e.g. `"fastpfor256"` or `"cpp_simple16"`.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Stable numeric ID.
+    #[must_use]
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// Creates a new instance of this codec.
+    #[must_use]
+    pub fn create(&self) -> Box<dyn DynCodec> {
+        (self.factory)()
+    }
+
+    /// Whether this codec can also compress `u64` values.
+    #[must_use]
+    pub fn supports_u64(&self) -> bool {
+        self.factory_u64.is_some()
+    }
+
+    /// Creates a new `u64` instance of this codec, or `None` if it only
+    /// handles `u32` values.
+    #[must_use]
+    pub fn create_u64(&self) -> Option<Box<dyn DynCodec<u64>>> {
+        self.factory_u64.map(|factory| factory())
+    }
+}
+
+fn create<C: DynCodec + Default + 'static>() -> Box<dyn DynCodec> {
+    Box::new(C::default())
+}
+
+fn create_u64<C: DynCodec<u64> + Default + 'static>() -> Box<dyn DynCodec<u64>> {
+    Box::new(C::default())
+}
+
+#[cfg(feature = "rust")]
+static RUST_CODECS: &[CodecInfo] = {
+    use crate::{CompositeCodec, FastPFor, FastPFor128, FastPFor256, JustCopy, VariableByte};
+    &[
+        CodecInfo::new("fastpfor256", 1, create::<FastPFor256>)
+            .with_u64(create_u64::<CompositeCodec<FastPFor<256, u64>, VariableByte>>),
+        CodecInfo::new("fastpfor128", 2, create::<FastPFor128>)
+            .with_u64(create_u64::<CompositeCodec<FastPFor<128, u64>, VariableByte>>),
+        CodecInfo::new("variablebyte", 3, create::<VariableByte>)
+            .with_u64(create_u64::<VariableByte>),
+        CodecInfo::new("justcopy", 4, create::<JustCopy>).with_u64(create_u64::<JustCopy>),
+    ]
+};
+
+#[cfg(not(feature = "rust"))]
+static RUST_CODECS: &[CodecInfo] = &[];
+
+#[cfg(feature = "cpp")]
+static CPP_CODECS: &[CodecInfo] = {
+    use crate::cpp;
+    &[
+        CodecInfo::new("cpp_bp32", 100, create::<cpp::CppBP32>),
+        CodecInfo::new("cpp_copy", 101, create::<cpp::CppCopy>),
+        CodecInfo::new(
+            "cpp_fastbinarypacking8",
+            102,
+            create::<cpp::CppFastBinaryPacking8>,
+        ),
+        CodecInfo::new(
+            "cpp_fastbinarypacking16",
+            103,
+            create::<cpp::CppFastBinaryPacking16>,
+        ),
+        CodecInfo::new(
+            "cpp_fastbinarypacking32",
+            104,
+            create::<cpp::CppFastBinaryPacking32>,
+        ),
+        CodecInfo::new("cpp_fastpfor128", 105, create::<cpp::CppFastPFor128>)
+            .with_u64(create_u64::<cpp::Cpp64<cpp::CppFastPFor128>>),
+        CodecInfo::new("cpp_fastpfor256", 106, create::<cpp::CppFastPFor256>)
+            .with_u64(create_u64::<cpp::Cpp64<cpp::CppFastPFor256>>),
+        CodecInfo::new("cpp_maskedvbyte", 107, create::<cpp::CppMaskedVByte>),
+        CodecInfo::new("cpp_newpfor", 108, create::<cpp::CppNewPFor>),
+        CodecInfo::new("cpp_optpfor", 109, create::<cpp::CppOptPFor>),
+        CodecInfo::new("cpp_pfor2008", 110, create::<cpp::CppPFor2008>),
+        CodecInfo::new("cpp_pfor", 111, create::<cpp::CppPFor>),
+        CodecInfo::new(
+            "cpp_simdbinarypacking",
+            112,
+            create::<cpp::CppSimdBinaryPacking>,
+        ),
+        CodecInfo::new(
+            "cpp_simdfastpfor128",
+            113,
+            create::<cpp::CppSimdFastPFor128>,
+        ),
+        CodecInfo::new(
+            "cpp_simdfastpfor256",
+            114,
+            create::<cpp::CppSimdFastPFor256>,
+        ),
+        CodecInfo::new(
+            "cpp_simdgroupsimple",
+            115,
+            create::<cpp::CppSimdGroupSimple>,
+        ),
+        CodecInfo::new(
+            "cpp_simdgroupsimple_ringbuf",
+            116,
+            create::<cpp::CppSimdGroupSimpleRingBuf>,
+        ),
+        CodecInfo::new("cpp_simdnewpfor", 117, create::<cpp::CppSimdNewPFor>),
+        CodecInfo::new("cpp_simdoptpfor", 118, create::<cpp::CppSimdOptPFor>),
+        CodecInfo::new("cpp_simdpfor", 119, create::<cpp::CppSimdPFor>),
+        CodecInfo::new("cpp_simdsimplepfor", 120, create::<cpp::CppSimdSimplePFor>),
+        CodecInfo::new("cpp_simple16", 121, create::<cpp::CppSimple16>),
+        CodecInfo::new("cpp_simple8b", 122, create::<cpp::CppSimple8b>),
+        CodecInfo::new("cpp_simple8b_rle", 123, create::<cpp::CppSimple8bRle>),
+        CodecInfo::new("cpp_simple9", 124, create::<cpp::CppSimple9>),
+        CodecInfo::new("cpp_simple9_rle", 125, create::<cpp::CppSimple9Rle>),
+        CodecInfo::new("cpp_simplepfor", 126, create::<cpp::CppSimplePFor>),
+        CodecInfo::new("cpp_streamvbyte", 127, create::<cpp::CppStreamVByte>),
+        CodecInfo::new("cpp_vbyte", 128, create::<cpp::CppVByte>),
+        CodecInfo::new("cpp_varint", 129, create::<cpp::CppVarInt>)
+            .with_u64(create_u64::<cpp::Cpp64<cpp::CppVarInt>>),
+        CodecInfo::new("cpp_varintgb", 130, create::<cpp::CppVarIntGb>),
+    ]
+};
+
+#[cfg(not(feature = "cpp"))]
+static CPP_CODECS: &[CodecInfo] = &[];
+
+/// Iterates over every codec available with the enabled features.
+pub fn all() -> impl Iterator<Item = &'static CodecInfo> {
+    RUST_CODECS.iter().chain(CPP_CODECS)
+}
+
+/// Looks up a codec by its stable name.
+#[must_use]
+pub fn info_by_name(name: &str) -> Option<&'static CodecInfo> {
+    all().find(|info| info.name == name)
+}
+
+/// Looks up a codec by its stable ID.
+#[must_use]
+pub fn info_by_id(id: u16) -> Option<&'static CodecInfo> {
+    all().find(|info| info.id == id)
+}
+
+/// Creates the codec with the given stable name, or `None` if it is unknown
+/// or its feature is disabled.
+#[must_use]
+pub fn from_name(name: &str) -> Option<Box<dyn DynCodec>> {
+    info_by_name(name).map(CodecInfo::create)
+}
+
+/// Creates the codec with the given stable ID, or `None` if it is unknown
+/// or its feature is disabled.
+#[must_use]
+pub fn from_id(id: u16) -> Option<Box<dyn DynCodec>> {
+    info_by_id(id).map(CodecInfo::create)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn names_and_ids_are_unique() {
+        let names: HashSet<_> = all().map(CodecInfo::name).collect();
+        let ids: HashSet<_> = all().map(CodecInfo::id).collect();
+        assert_eq!(names.len(), all().count());
+        assert_eq!(ids.len(), all().count());
+    }
+
+    #[test]
+    fn every_codec_roundtrips() {
+        let data: Vec<u32> = (0..300u32).map(|i| i * 13 % 211).collect();
+        for info in all() {
+            let mut encoded = Vec::new();
+            info.create().encode_dyn(&data, &mut encoded).unwrap();
+            let mut decoded = Vec::new();
+            from_id(info.id())
+                .unwrap()
+                .decode_dyn(&encoded, &mut decoded, Some(300))
+                .unwrap();
+            assert_eq!(decoded, data, "{}", info.name());
+        }
+    }
+
+    #[test]
+    fn u64_codecs_roundtrip() {
+        let data: Vec<u64> = (0..300u64).map(|i| i << 40 | i).collect();
+        for info in all().filter(|info| info.supports_u64()) {
+            let mut encoded = Vec::new();
+            info.create_u64()
+                .unwrap()
+                .encode_dyn(&data, &mut encoded)
+                .unwrap();
+            let mut decoded = Vec::new();
+            info.create_u64()
+                .unwrap()
+                .decode_dyn(&encoded, &mut decoded, Some(300))
+                .unwrap();
+            assert_eq!(decoded, data, "{}", info.name());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rust")]
+    fn rust_codecs_keep_their_ids() {
+        assert_eq!(info_by_name("fastpfor256").unwrap().id(), 1);
+        assert_eq!(info_by_id(3).unwrap().name(), "variablebyte");
+    }
+
+    #[test]
+    #[cfg(feature = "cpp")]
+    fn cpp_codecs_keep_their_ids() {
+        assert_eq!(info_by_name("cpp_simple16").unwrap().id(), 121);
+        assert_eq!(info_by_id(105).unwrap().name(), "cpp_fastpfor128");
+    }
+
+    #[test]
+    fn unknown_codecs_are_none() {
+        assert!(from_name("nope").is_none());
+        assert!(from_id(u16::MAX).is_none());
+    }
+}
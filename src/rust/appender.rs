@@ -0,0 +1,326 @@
+//! [`Appender`]: continues a `FastPFor` + `VariableByte` stream without
+//! re-encoding the values it already holds.
+
+use alloc::vec::Vec;
+
+use crate::codec::AnyLenCodec;
+use crate::helpers::AsUsize;
+use crate::rust::integer_compression::fastpfor::sealed::BlockSize;
+use crate::rust::{FastPFor, FastPForConfig, VariableByte};
+use crate::{FastPForError, FastPForResult};
+
+/// [`Appender`] for [`FastPFor256`](crate::FastPFor256) streams.
+pub type FastPFor256Appender = Appender<256>;
+
+/// [`Appender`] for [`FastPFor128`](crate::FastPFor128) streams.
+pub type FastPFor128Appender = Appender<128>;
+
+/// Appends values to a stream in the format written by
+/// [`CompositeCodec<FastPFor<N>, VariableByte>`](crate::CompositeCodec).
+///
+/// Each [`append`](Self::append) decodes only the `VariableByte` tail, which
+/// is encoded again together with the new values, and updates the block-count
+/// header. If the last page is not full, new blocks are added to it: its packed
+/// blocks and metadata are kept as they are, and only its exceptions are packed
+/// again. Full pages are never touched, so the cost of an append does not grow
+/// with the stream.
+///
+/// Because the last page is refilled rather than followed by a short one, the
+/// result has the same pages as encoding all the values at once, and any
+/// decoder for the matching composite can read it. The stream must be continued with the
+/// configuration it was written with.
+///
+/// # Example
+///
+/// ```
+/// use fastpfor::{AnyLenCodec, FastPFor256, FastPFor256Appender};
+///
+/// let mut appender = FastPFor256Appender::default();
+/// for chunk in (0..10_000u32).collect::<Vec<_>>().chunks(3000) {
+///     appender.append(chunk).unwrap();
+/// }
+/// assert_eq!(appender.value_count(), 10_000);
+///
+/// let mut decoded = Vec::new();
+/// FastPFor256::default()
+///     .decode(appender.as_slice(), &mut decoded, None)
+///     .unwrap();
+/// assert_eq!(decoded, (0..10_000).collect::<Vec<_>>());
+/// ```
+pub struct Appender<const N: usize> {
+    codec: FastPFor<N>,
+    stream: Vec<u32>,
+    /// Position in `stream` of the last page, or of the tail if there is none
+    last_page_start: usize,
+    /// Position in `stream` of the `VariableByte` tail
+    tail_start: usize,
+    /// Number of values in the tail
+    tail_len: usize,
+}
+
+impl<const N: usize> Default for Appender<N>
+where
+    [u32; N]: BlockSize,
+{
+    fn default() -> Self {
+        Self {
+            codec: FastPFor::default(),
+            stream: Vec::from([0]),
+            last_page_start: 1,
+            tail_start: 1,
+            tail_len: 0,
+        }
+    }
+}
+
+impl<const N: usize> Appender<N>
+where
+    [u32; N]: BlockSize,
+{
+    /// Continues `encoded`, written with the default configuration.
+    ///
+    /// Walks the page headers to find the last page and the tail, and checks
+    /// that the tail decodes; the pages themselves are not unpacked.
+    pub fn new(encoded: Vec<u32>) -> FastPForResult<Self> {
+        Self::from_config(FastPForConfig::default(), encoded)
+    }
+
+    /// Continues `encoded`, written with `config`.
+    pub fn from_config(config: FastPForConfig<N>, mut encoded: Vec<u32>) -> FastPForResult<Self> {
+        if encoded.is_empty() {
+            encoded.push(0);
+        }
+        let mut codec = FastPFor::from_config(config);
+        let block_values = FastPFor::<N>::read_length_header(&encoded, None)?;
+        let (last_page_start, tail_start) = codec.skip_pages(&encoded, block_values)?;
+
+        let mut tail: Vec<u32> = Vec::new();
+        VariableByte::new().decode(
+            encoded
+                .get(tail_start..)
+                .ok_or(FastPForError::NotEnoughData)?,
+            &mut tail,
+            None,
+        )?;
+        if tail.len() >= N {
+            return Err(FastPForError::NotEnoughData);
+        }
+        Ok(Self {
+            codec,
+            stream: encoded,
+            last_page_start,
+            tail_start,
+            tail_len: tail.len(),
+        })
+    }
+
+    /// Appends `values` to the stream.
+    ///
+    /// On error the stream is left unchanged.
+    pub fn append(&mut self, values: &[u32]) -> FastPForResult<()> {
+        let page_values = self.codec.values_per_page();
+        let block_values = self.stream[0].as_usize();
+        let partial = block_values % page_values;
+
+        // Values that must be encoded: the tail, then the new values.
+        let mut carry = Vec::with_capacity(self.tail_len + values.len());
+        VariableByte::new().decode(&self.stream[self.tail_start..], &mut carry, None)?;
+        carry.extend_from_slice(values);
+
+        let carry_blocks = carry.len() / N * N;
+        let total = block_values + carry_blocks;
+        let new_header =
+            u32::try_from(total).map_err(|_| FastPForError::InvalidInputLength(total))?;
+
+        // Everything from `suffix_start` on is rebuilt in `suffix`, and only
+        // replaces the end of the stream once it is complete.
+        let mut suffix = Vec::new();
+        let mut last_page_start = self.last_page_start;
+        let (suffix_start, new_blocks) = if partial != 0 && carry_blocks != 0 {
+            let fill = (page_values - partial).min(carry_blocks);
+            self.codec.extend_page(
+                &self.stream[self.last_page_start..self.tail_start],
+                block_values / page_values,
+                partial,
+                &carry[..fill],
+                &mut suffix,
+            )?;
+            (self.last_page_start, &carry[fill..carry_blocks])
+        } else {
+            (self.tail_start, &carry[..carry_blocks])
+        };
+        let mut page_starts = Vec::new();
+        self.codec
+            .encode_pages(new_blocks, &mut suffix, &mut page_starts)?;
+        if let Some(&last) = page_starts.last() {
+            last_page_start = suffix_start + last.as_usize();
+        }
+        let tail_start = suffix_start + suffix.len();
+        VariableByte::new().encode(&carry[carry_blocks..], &mut suffix)?;
+
+        self.stream.truncate(suffix_start);
+        self.stream.extend_from_slice(&suffix);
+        self.stream[0] = new_header;
+        self.last_page_start = last_page_start;
+        self.tail_start = tail_start;
+        self.tail_len = carry.len() - carry_blocks;
+        Ok(())
+    }
+
+    /// Number of values in the stream.
+    #[must_use]
+    pub fn value_count(&self) -> usize {
+        self.stream[0].as_usize() + self.tail_len
+    }
+
+    /// The encoded stream so far.
+    #[must_use]
+    pub fn as_slice(&self) -> &[u32] {
+        &self.stream
+    }
+
+    /// Returns the encoded stream.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<u32> {
+        self.stream
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{compress, decompress};
+    use crate::{FastPFor128, FastPFor256};
+
+    fn data(len: u32) -> Vec<u32> {
+        (0..len)
+            .map(|i| i.wrapping_mul(2_654_435_761) >> (i % 29))
+            .collect()
+    }
+
+    /// Unused bits in the last group of packed exceptions are not specified,
+    /// so compare lengths and decoded values rather than words.
+    fn assert_same_stream(actual: &[u32], data: &[u32], expected: &[u32]) {
+        assert_eq!(actual.len(), expected.len());
+        assert_eq!(actual[0], expected[0]);
+        assert_eq!(decompress::<FastPFor256>(actual, None).unwrap(), data);
+    }
+
+    #[test]
+    fn test_chunked_appends_match_one_shot() {
+        let data = data(140_000);
+        for chunk_len in [255, 256, 3000, 70_000] {
+            let mut appender = FastPFor256Appender::default();
+            for chunk in data.chunks(chunk_len) {
+                appender.append(chunk).unwrap();
+            }
+            assert_eq!(appender.value_count(), data.len());
+            assert_same_stream(
+                appender.as_slice(),
+                &data,
+                &compress::<FastPFor256>(&data).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_continue_existing_stream() {
+        let data = data(150_000);
+        for split in [0, 10, 256, 65_536, 65_600, 131_072, 140_001] {
+            let encoded = compress::<FastPFor128>(&data[..split]).unwrap();
+            let mut appender = FastPFor128Appender::new(encoded).unwrap();
+            assert_eq!(appender.value_count(), split);
+            appender.append(&data[split..]).unwrap();
+            let encoded = appender.into_inner();
+            let expected = compress::<FastPFor128>(&data).unwrap();
+            assert_eq!(encoded.len(), expected.len());
+            assert_eq!(decompress::<FastPFor128>(&encoded, None).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_empty_slice_is_empty_stream() {
+        let mut appender = FastPFor256Appender::new(Vec::new()).unwrap();
+        appender.append(&[]).unwrap();
+        assert_eq!(appender.as_slice(), [0]);
+        appender.append(&[1, 2, 3]).unwrap();
+        assert_eq!(
+            appender.as_slice(),
+            compress::<FastPFor256>(&[1, 2, 3]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_checksummed_pages() {
+        let config = FastPForConfig::new(1024).unwrap().with_checksums(true);
+        let data = data(10_000);
+        let mut codec =
+            crate::CompositeCodec::new(FastPFor::from_config(config), VariableByte::new());
+        let mut expected = Vec::new();
+        codec.encode(&data, &mut expected).unwrap();
+
+        let mut appender = Appender::<256>::from_config(config, Vec::new()).unwrap();
+        for chunk in data.chunks(900) {
+            appender.append(chunk).unwrap();
+        }
+        assert_eq!(appender.as_slice().len(), expected.len());
+        let mut decoded = Vec::new();
+        codec
+            .decode(appender.as_slice(), &mut decoded, None)
+            .unwrap();
+        assert_eq!(decoded, data);
+
+        let mut appender = Appender::<256>::from_config(config, expected).unwrap();
+        appender.append(&[7; 300]).unwrap();
+        decoded.clear();
+        codec
+            .decode(appender.as_slice(), &mut decoded, None)
+            .unwrap();
+        assert_eq!(decoded[..10_000], data);
+        assert_eq!(decoded[10_000..], [7; 300]);
+    }
+
+    #[test]
+    fn test_open_page_blocks_are_kept() {
+        let data = data(2000);
+        let mut appender = FastPFor256Appender::default();
+        appender.append(&data[..600]).unwrap();
+        let page = appender.last_page_start;
+        let packed = appender.stream[page + 1..page + appender.stream[page] as usize].to_vec();
+
+        appender.append(&data[600..]).unwrap();
+        assert_eq!(appender.last_page_start, page);
+        assert_eq!(appender.stream[page + 1..][..packed.len()], packed);
+        assert_same_stream(
+            appender.as_slice(),
+            &data,
+            &compress::<FastPFor256>(&data).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_failed_append_leaves_stream_unchanged() {
+        let config = FastPForConfig::new(1024).unwrap().with_checksums(true);
+        let mut appender = Appender::<256>::from_config(config, Vec::new()).unwrap();
+        // Two full pages, then an open page of 512 values.
+        appender.append(&data(2600)).unwrap();
+        // Corrupt a packed word of the open page, under its checksum.
+        appender.stream[appender.last_page_start + 3] ^= 1;
+        let before = appender.as_slice().to_vec();
+
+        assert!(matches!(
+            appender.append(&data(600)),
+            Err(FastPForError::ChecksumMismatch { page: 2 })
+        ));
+        assert_eq!(appender.as_slice(), before);
+        assert_eq!(appender.value_count(), 2600);
+    }
+
+    #[test]
+    fn test_corrupt_stream_errors() {
+        let mut encoded = compress::<FastPFor256>(&data(1000)).unwrap();
+        encoded.truncate(encoded.len() / 2);
+        assert!(FastPFor256Appender::new(encoded).is_err());
+        assert!(FastPFor256Appender::new(vec![255]).is_err());
+    }
+}
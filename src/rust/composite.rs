@@ -3,13 +3,19 @@
 //!
 //! Rust-only: combines Rust block codecs with Rust tail codecs. Do not wrap C++ codecs.
 
-use crate::FastPForResult;
-use crate::codec::{AnyLenCodec, BlockCodec, slice_to_blocks};
+use alloc::vec::Vec;
+
+use crate::codec::{AnyLenCodec, BlockCodec, slice_to_blocks_of};
 use crate::helpers::AsUsize;
+use crate::rust::integer_compression::fastpfor::sealed::BlockSize;
+use crate::rust::stream_info::StreamInfo;
+use crate::rust::{FastPFor, VariableByte};
+use crate::{Element, FastPForResult};
 
 /// Combines a block-oriented codec with an arbitrary-length tail codec.
 ///
-/// `CompositeCodec<Blocks, Tail>` implements [`AnyLenCodec`]: it accepts any
+/// `CompositeCodec<Blocks, Tail>` implements [`AnyLenCodec<T>`] for every
+/// element type `T` that `Blocks` and `Tail` both support: it accepts any
 /// input length, encodes the aligned prefix with `Blocks`, and the
 /// sub-block remainder with `Tail`.
 ///
@@ -40,37 +46,58 @@ use crate::helpers::AsUsize;
 /// codec.decode(&encoded, &mut decoded, None).unwrap();
 /// assert_eq!(decoded, data);
 /// ```
-pub struct CompositeCodec<Blocks: BlockCodec, Tail: AnyLenCodec> {
-    block: Blocks,
+pub struct CompositeCodec<Blocks, Tail> {
+    pub(crate) block: Blocks,
     tail: Tail,
 }
 
-impl<Blocks: BlockCodec, Tail: AnyLenCodec> Default for CompositeCodec<Blocks, Tail> {
+impl<Blocks: Default, Tail: Default> Default for CompositeCodec<Blocks, Tail> {
     fn default() -> Self {
         Self::new(Blocks::default(), Tail::default())
     }
 }
 
-impl<Blocks: BlockCodec, Tail: AnyLenCodec> CompositeCodec<Blocks, Tail> {
+impl<Blocks, Tail> CompositeCodec<Blocks, Tail> {
     /// Creates a new `CompositeCodec` from a block codec and a tail codec.
     pub fn new(block: Blocks, tail: Tail) -> Self {
         Self { block, tail }
     }
 }
 
-impl<Blocks: BlockCodec, Tail: AnyLenCodec> AnyLenCodec for CompositeCodec<Blocks, Tail> {
-    fn encode(&mut self, input: &[u32], out: &mut Vec<u32>) -> FastPForResult<()> {
-        let (blocks, remainder) = slice_to_blocks::<Blocks>(input);
+impl<T, Blocks, Tail> AnyLenCodec<T> for CompositeCodec<Blocks, Tail>
+where
+    T: Element,
+    Blocks: BlockCodec<T>,
+    Tail: AnyLenCodec<T>,
+{
+    fn encode(&mut self, input: &[T], out: &mut Vec<u32>) -> FastPForResult<()> {
+        let (blocks, remainder) = slice_to_blocks_of::<Blocks, T>(input);
         // C++ CompositeCodec: concatenate block + tail. Block codec writes length header (0 when empty).
         self.block.encode_blocks(blocks, out)?;
         self.tail.encode(remainder, out)
     }
 
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        let remainder = n_values % Blocks::size();
+        self.block.max_compressed_len(n_values - remainder)
+            + self.tail.max_compressed_len(remainder)
+    }
+
+    fn page_len(&self) -> Option<usize> {
+        self.block.page_len()
+    }
+
+    fn encode_into(&mut self, input: &[T], out: &mut [u32]) -> FastPForResult<usize> {
+        let (blocks, remainder) = slice_to_blocks_of::<Blocks, T>(input);
+        let written = self.block.encode_blocks_into(blocks, out)?;
+        Ok(written + self.tail.encode_into(remainder, &mut out[written..])?)
+    }
+
     /// Decode C++ format: `[block_data][tail_data]`. Block codec's first word = block value count.
     fn decode(
         &mut self,
         input: &[u32],
-        out: &mut Vec<u32>,
+        out: &mut Vec<T>,
         expected_len: Option<u32>,
     ) -> FastPForResult<()> {
         let start_len = out.len();
@@ -103,13 +130,63 @@ impl<Blocks: BlockCodec, Tail: AnyLenCodec> AnyLenCodec for CompositeCodec<Block
 
         Ok(())
     }
+
+    fn decode_into(&mut self, input: &[u32], out: &mut [T]) -> FastPForResult<usize> {
+        if input.is_empty() {
+            return self.tail.decode_into(&[], out);
+        }
+        let (consumed, written) = self.block.decode_blocks_into(input, None, out)?;
+        let tail_written = self
+            .tail
+            .decode_into(&input[consumed..], &mut out[written..])?;
+        Ok(written + tail_written)
+    }
+}
+
+impl<const N: usize> CompositeCodec<FastPFor<N>, VariableByte>
+where
+    [u32; N]: BlockSize,
+{
+    /// Checks that `input` is a well-formed stream without decoding it.
+    ///
+    /// Validates the `FastPFor` pages with [`FastPFor::validate`] and counts the
+    /// `VariableByte` tail; see [`StreamInfo`]. Like
+    /// [`decode`](AnyLenCodec::decode), an empty input is an empty stream.
+    ///
+    /// ```
+    /// use fastpfor::{AnyLenCodec, FastPFor256};
+    ///
+    /// let data: Vec<u32> = (0..1000).collect();
+    /// let mut codec = FastPFor256::default();
+    /// let mut encoded = Vec::new();
+    /// codec.encode(&data, &mut encoded).unwrap();
+    ///
+    /// let info = codec.validate(&encoded).unwrap();
+    /// assert_eq!((info.value_count(), info.page_count()), (1000, 1));
+    /// ```
+    pub fn validate(&mut self, input: &[u32]) -> FastPForResult<StreamInfo> {
+        if input.is_empty() {
+            return Ok(StreamInfo::default());
+        }
+        let blocks = self.block.validate(input)?;
+        let tail = self.tail.validate(&input[blocks.consumed_words()..]);
+        Ok(StreamInfo::new(
+            blocks.value_count() + tail.value_count(),
+            blocks.page_count(),
+            input.len(),
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rust::FastPFor;
     use crate::rust::{FastPForBlock128, FastPForBlock256, JustCopy, VariableByte};
-    use crate::test_utils::{compress, decompress, roundtrip_composite, roundtrip_expected};
+    use crate::test_utils::{
+        compress, decompress, roundtrip_composite, roundtrip_elements, roundtrip_expected,
+        roundtrip_into,
+    };
     use crate::{FastPFor128, FastPFor256};
 
     #[test]
@@ -226,4 +303,35 @@ mod tests {
         let huge = (FastPFor256::max_decompressed_len(encoded.len()) + 1) as u32;
         decompress::<FastPFor256>(&encoded, Some(huge)).unwrap_err();
     }
+
+    #[test]
+    fn test_narrow_elements_with_remainder() {
+        let data: Vec<u16> = (0..600u32).map(|i| (i * 97) as u16).collect();
+        roundtrip_elements::<u16, CompositeCodec<FastPFor<256, u16>, VariableByte>>(&data);
+
+        let data: Vec<u8> = (0..300u32).map(|i| i as u8).collect();
+        roundtrip_elements::<u8, CompositeCodec<FastPFor<128, u8>, JustCopy>>(&data);
+    }
+
+    #[test]
+    fn test_wide_and_signed_elements_with_remainder() {
+        let data: Vec<u64> = (0..600u64).map(|i| i * 0x1_0000_0001).collect();
+        roundtrip_elements::<u64, CompositeCodec<FastPFor<256, u64>, VariableByte>>(&data);
+
+        let data: Vec<i64> = (0..300i64).map(|i| i - 150).collect();
+        roundtrip_elements::<i64, CompositeCodec<FastPFor<128, i64>, JustCopy>>(&data);
+
+        let data: Vec<i32> = (0..300i32).map(|i| i * -7).collect();
+        roundtrip_elements::<i32, CompositeCodec<FastPFor<128, i32>, VariableByte>>(&data);
+    }
+
+    #[test]
+    fn test_decode_into_slice_with_remainder() {
+        let data: Vec<u32> = (0..600u32).map(|i| i * 31 % 977).collect();
+        roundtrip_into::<u32, FastPFor256>(&data);
+        roundtrip_into::<u32, CompositeCodec<FastPForBlock128, JustCopy>>(&data);
+
+        let data: Vec<u64> = (0..300u64).map(|i| i << 40).collect();
+        roundtrip_into::<u64, CompositeCodec<FastPFor<128, u64>, VariableByte>>(&data);
+    }
 }
@@ -0,0 +1,58 @@
+//! CRC-32C (Castagnoli), used for optional per-page checksums.
+
+/// Reflected CRC-32C polynomial.
+const POLY: u32 = 0x82F6_3B78;
+
+/// Byte-at-a-time lookup table, built at compile time.
+const TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ POLY
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn update(crc: u32, bytes: &[u8]) -> u32 {
+    bytes.iter().fold(crc, |crc, &b| {
+        TABLE[((crc ^ u32::from(b)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// CRC-32C of `words` serialized as little-endian bytes, so that the checksum
+/// is the same on every host.
+pub fn crc32c_words(words: &[u32]) -> u32 {
+    !words
+        .iter()
+        .fold(!0, |crc, word| update(crc, &word.to_le_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_value() {
+        // Standard CRC-32C check value for "123456789"; words are hashed as
+        // their little-endian bytes.
+        assert_eq!(!update(!0, b"123456789"), 0xE306_9283);
+        let words = [u32::from_le_bytes(*b"1234"), u32::from_le_bytes(*b"5678")];
+        assert_eq!(crc32c_words(&words), !update(!0, b"12345678"));
+    }
+
+    #[test]
+    fn test_empty_is_zero() {
+        assert_eq!(crc32c_words(&[]), 0);
+    }
+}
@@ -1,18 +1,43 @@
-use std::io::Cursor;
+use core::marker::PhantomData;
 
-/// Extension trait for `Cursor<u32>` providing position increment operations.
-pub trait IncrementCursor {
-    /// Increments the cursor position by 1.
-    fn increment(&mut self);
-    /// Adds `n` to the cursor position.
-    fn add(&mut self, n: u32);
+/// Position within a buffer of `T` words.
+///
+/// A `core`-only replacement for the `std::io::Cursor<u32>` that the codecs
+/// used purely for position tracking. It keeps the same `position` /
+/// `set_position` API, but holds no buffer: [`new`](Self::new) takes the
+/// starting position.
+#[derive(Debug)]
+pub struct Cursor<T> {
+    position: u64,
+    _word: PhantomData<T>,
 }
 
-impl IncrementCursor for Cursor<u32> {
-    fn increment(&mut self) {
-        self.set_position(self.position() + 1); // Position needs to be a u64
+impl Cursor<u32> {
+    /// Creates a cursor at `position`.
+    pub const fn new(position: u32) -> Self {
+        Self {
+            position: position as u64,
+            _word: PhantomData,
+        }
+    }
+
+    /// Returns the current position.
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Moves the cursor to `position`.
+    pub fn set_position(&mut self, position: u64) {
+        self.position = position;
     }
-    fn add(&mut self, n: u32) {
-        self.set_position(self.position() + u64::from(n));
+
+    /// Increments the cursor position by 1.
+    pub fn increment(&mut self) {
+        self.position += 1;
+    }
+
+    /// Adds `n` to the cursor position.
+    pub fn add(&mut self, n: u32) {
+        self.position += u64::from(n);
     }
 }
@@ -0,0 +1,459 @@
+//! Incremental decoding: the lazy iterators [`FastPForIter`] and
+//! [`VariableByteIter`], and the block visitor `decode_with`. Both decode a
+//! compressed stream one page (or one value) at a time, so memory use stays
+//! bounded by the page size rather than the stream length.
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::ControlFlow;
+
+use crate::rust::integer_compression::fastpfor::sealed::BlockSize;
+use crate::rust::integer_compression::variable_byte::WordBytes;
+use crate::rust::{CompositeCodec, FastPFor, VariableByte};
+use crate::{Element, FastPForError, FastPForResult};
+
+impl VariableByte {
+    /// Lazily decodes a [`VariableByte`] stream, one value at a time.
+    ///
+    /// Yields the same values as [`decode`](crate::AnyLenCodec::decode) without
+    /// allocating or copying the input. After the first error the iterator is exhausted.
+    ///
+    /// ```
+    /// use fastpfor::{AnyLenCodec, VariableByte};
+    ///
+    /// let data: Vec<u32> = (0..100).collect();
+    /// let mut encoded = Vec::new();
+    /// VariableByte::new().encode(&data, &mut encoded).unwrap();
+    ///
+    /// let sum: u32 = VariableByte::decode_iter::<u32>(&encoded)
+    ///     .sum::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(sum, 4950);
+    /// ```
+    #[must_use]
+    pub fn decode_iter<T: Element>(input: &[u32]) -> VariableByteIter<'_, T> {
+        VariableByteIter::new(input)
+    }
+}
+
+impl<const N: usize, T: Element> CompositeCodec<FastPFor<N, T>, VariableByte>
+where
+    [u32; N]: BlockSize,
+{
+    /// Lazily decodes a stream written by [`encode`](crate::AnyLenCodec::encode),
+    /// one page at a time.
+    ///
+    /// Each page of blocks is decoded into a buffer owned by the iterator, so
+    /// memory use is bounded by the codec's page size however long the stream
+    /// is. The sub-block remainder is then decoded one value at a time. Pages
+    /// must have been encoded with the same page size as this codec's.
+    ///
+    /// Yields the same values as [`decode`](crate::AnyLenCodec::decode). After
+    /// the first error the iterator is exhausted.
+    ///
+    /// ```
+    /// use fastpfor::{AnyLenCodec, FastPFor256};
+    ///
+    /// let data: Vec<u32> = (0..1000).collect();
+    /// let mut codec = FastPFor256::default();
+    /// let mut encoded = Vec::new();
+    /// codec.encode(&data, &mut encoded).unwrap();
+    ///
+    /// let max = codec.decode_iter(&encoded).map(Result::unwrap).max();
+    /// assert_eq!(max, Some(999));
+    /// ```
+    pub fn decode_iter<'a>(&'a mut self, input: &'a [u32]) -> FastPForIter<'a, N, T> {
+        FastPForIter::new(&mut self.block, input)
+    }
+
+    /// Decodes a stream written by [`encode`](crate::AnyLenCodec::encode),
+    /// passing each block of values to `f` instead of collecting them.
+    ///
+    /// Works like [`FastPFor::decode_with`]. The remainder after the last full
+    /// block is decoded into the same `N`-value scratch buffer and passed as a
+    /// final, shorter block.
+    ///
+    /// ```
+    /// use std::ops::ControlFlow;
+    ///
+    /// use fastpfor::{AnyLenCodec, FastPFor128};
+    ///
+    /// let data: Vec<u32> = (0..1000).collect();
+    /// let mut codec = FastPFor128::default();
+    /// let mut encoded = Vec::new();
+    /// codec.encode(&data, &mut encoded).unwrap();
+    ///
+    /// let mut sum = 0u64;
+    /// codec
+    ///     .decode_with(&encoded, |block| {
+    ///         sum += block.iter().map(|&v| u64::from(v)).sum::<u64>();
+    ///         ControlFlow::<()>::Continue(())
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(sum, 499_500);
+    /// ```
+    pub fn decode_with<B>(
+        &mut self,
+        input: &[u32],
+        mut f: impl FnMut(&[T]) -> ControlFlow<B>,
+    ) -> FastPForResult<ControlFlow<B>> {
+        if input.is_empty() {
+            return Ok(ControlFlow::Continue(()));
+        }
+        let (flow, consumed) = self.block.visit_blocks(input, &mut f)?;
+        if flow.is_break() {
+            return Ok(flow);
+        }
+        let mut block = [T::default(); N];
+        let mut len = 0;
+        for value in VariableByteIter::new(&input[consumed..]) {
+            block[len] = value?;
+            len += 1;
+            if len == N {
+                if let ControlFlow::Break(b) = f(&block) {
+                    return Ok(ControlFlow::Break(b));
+                }
+                len = 0;
+            }
+        }
+        if len == 0 {
+            return Ok(ControlFlow::Continue(()));
+        }
+        Ok(f(&block[..len]))
+    }
+}
+
+/// Iterator returned by [`VariableByte::decode_iter`].
+#[derive(Debug, Clone)]
+pub struct VariableByteIter<'a, T: Element = u32> {
+    bytes: WordBytes<'a>,
+    _element: PhantomData<T>,
+}
+
+impl<'a, T: Element> VariableByteIter<'a, T> {
+    fn new(input: &'a [u32]) -> Self {
+        Self {
+            bytes: WordBytes::new(input),
+            _element: PhantomData,
+        }
+    }
+}
+
+impl<T: Element> Iterator for VariableByteIter<'_, T> {
+    type Item = FastPForResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = VariableByte::decode_value(&mut self.bytes)?;
+        if value.is_err() {
+            self.bytes = WordBytes::new(&[]);
+        }
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.bytes.size_hint().1)
+    }
+}
+
+impl<T: Element> FusedIterator for VariableByteIter<'_, T> {}
+
+/// Iterator returned by `FastPFor256::decode_iter` and `FastPFor128::decode_iter`.
+#[derive(Debug)]
+pub struct FastPForIter<'a, const N: usize, T: Element = u32> {
+    codec: &'a mut FastPFor<N, T>,
+    input: &'a [u32],
+    /// Position of the next page in `input`
+    offset: usize,
+    /// Block values not yet decoded into `page`
+    remaining: usize,
+    /// Index of the next page in the stream
+    page_index: usize,
+    /// Values of the current page
+    page: Vec<T>,
+    /// Position of the next value in `page`
+    page_pos: usize,
+    /// Remainder decoder, set once all pages have been decoded
+    tail: Option<VariableByteIter<'a, T>>,
+    /// Header error to report on the first call to `next`
+    error: Option<FastPForError>,
+}
+
+impl<'a, const N: usize, T: Element> FastPForIter<'a, N, T>
+where
+    [u32; N]: BlockSize,
+{
+    fn new(codec: &'a mut FastPFor<N, T>, input: &'a [u32]) -> Self {
+        let mut iter = Self {
+            codec,
+            input,
+            offset: 1,
+            remaining: 0,
+            page_index: 0,
+            page: Vec::new(),
+            page_pos: 0,
+            tail: None,
+            error: None,
+        };
+        if input.is_empty() {
+            // Matches `decode`: an empty slice is a stream with no values.
+            iter.finish();
+        } else {
+            match FastPFor::<N, T>::read_length_header(input, None) {
+                Ok(n_values) => iter.remaining = n_values,
+                Err(err) => {
+                    iter.error = Some(err);
+                    iter.finish();
+                }
+            }
+        }
+        iter
+    }
+
+    /// Ends the iteration after the current page.
+    fn finish(&mut self) {
+        self.remaining = 0;
+        self.tail = Some(VariableByteIter::new(&[]));
+    }
+}
+
+impl<const N: usize, T: Element> Iterator for FastPForIter<'_, N, T>
+where
+    [u32; N]: BlockSize,
+{
+    type Item = FastPForResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.error.take() {
+            return Some(Err(err));
+        }
+        loop {
+            if let Some(&value) = self.page.get(self.page_pos) {
+                self.page_pos += 1;
+                return Some(Ok(value));
+            }
+            if self.remaining == 0 {
+                break;
+            }
+            self.page.clear();
+            self.page_pos = 0;
+            let input = &self.input[self.offset..];
+            match self.codec.decode_next_page(
+                input,
+                self.page_index,
+                &mut self.remaining,
+                &mut self.page,
+            ) {
+                Ok(consumed) => {
+                    self.offset += consumed;
+                    self.page_index += 1;
+                }
+                Err(err) => {
+                    self.finish();
+                    return Some(Err(err));
+                }
+            }
+        }
+        self.tail
+            .get_or_insert_with(|| VariableByteIter::new(&self.input[self.offset..]))
+            .next()
+    }
+}
+
+impl<const N: usize, T: Element> FusedIterator for FastPForIter<'_, N, T> where [u32; N]: BlockSize {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{compress, decompress, decompress_elements, get_test_cases};
+    use crate::{AnyLenCodec as _, FastPFor128, FastPFor256};
+
+    fn collect<I: Iterator<Item = FastPForResult<T>>, T>(iter: I) -> FastPForResult<Vec<T>> {
+        iter.collect()
+    }
+
+    #[test]
+    fn test_iter_matches_decode() {
+        for n in [0, 1, 127, 128, 300, 1000] {
+            for data in get_test_cases(n) {
+                let encoded = compress::<FastPFor256>(&data).unwrap();
+                let mut codec = FastPFor256::default();
+                assert_eq!(collect(codec.decode_iter(&encoded)).unwrap(), data);
+
+                let encoded = compress::<FastPFor128>(&data).unwrap();
+                let mut codec = FastPFor128::default();
+                assert_eq!(collect(codec.decode_iter(&encoded)).unwrap(), data);
+
+                let encoded = compress::<VariableByte>(&data).unwrap();
+                let iter = VariableByte::decode_iter::<u32>(&encoded);
+                assert_eq!(collect(iter).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_across_pages() {
+        // A 256-value page size splits 1000 values into several pages plus a tail.
+        let data: Vec<u32> = (0..1000u32).map(|i| i * 7919 % 4099).collect();
+        let mut codec = CompositeCodec::new(FastPFor::<128>::new(256).unwrap(), VariableByte);
+        let mut encoded = Vec::new();
+        codec.encode(&data, &mut encoded).unwrap();
+        assert_eq!(collect(codec.decode_iter(&encoded)).unwrap(), data);
+        assert!(codec.decode_iter(&encoded).page.capacity() <= 256);
+    }
+
+    #[test]
+    fn test_iter_wide_elements() {
+        let data: Vec<u64> = (0..600u64)
+            .map(|i| if i % 5 == 0 { u64::MAX - i } else { i })
+            .collect();
+        let mut codec = CompositeCodec::<FastPFor<256, u64>, VariableByte>::default();
+        let mut encoded = Vec::new();
+        codec.encode(&data, &mut encoded).unwrap();
+        assert_eq!(collect(codec.decode_iter(&encoded)).unwrap(), data);
+
+        let mut encoded = Vec::new();
+        VariableByte::new().encode(&data, &mut encoded).unwrap();
+        let decoded = decompress_elements::<u64, VariableByte>(&encoded, None).unwrap();
+        assert_eq!(
+            collect(VariableByte::decode_iter::<u64>(&encoded)).unwrap(),
+            decoded
+        );
+    }
+
+    #[test]
+    fn test_iter_narrow_out_of_range_stops() {
+        let encoded = compress::<VariableByte>(&[1, 70_000, 2]).unwrap();
+        let mut iter = VariableByte::decode_iter::<u16>(&encoded);
+        assert_eq!(iter.next().unwrap().unwrap(), 1);
+        assert!(matches!(
+            iter.next(),
+            Some(Err(FastPForError::ValueOutOfRange(70_000)))
+        ));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_decode_with_visits_every_block() {
+        let data: Vec<u32> = (0..1000u32).map(|i| i * 31 % 977).collect();
+        let mut codec = CompositeCodec::new(FastPFor::<128>::new(256).unwrap(), VariableByte);
+        let mut encoded = Vec::new();
+        codec.encode(&data, &mut encoded).unwrap();
+
+        let mut lens = Vec::new();
+        let mut decoded = Vec::new();
+        let flow = codec
+            .decode_with(&encoded, |block| {
+                lens.push(block.len());
+                decoded.extend_from_slice(block);
+                ControlFlow::<()>::Continue(())
+            })
+            .unwrap();
+        assert!(flow.is_continue());
+        assert_eq!(decoded, data);
+        // 7 full blocks, then the 104-value remainder.
+        assert_eq!(lens, [128, 128, 128, 128, 128, 128, 128, 104]);
+    }
+
+    #[test]
+    fn test_decode_with_stops_early() {
+        let data: Vec<u32> = (0..1000).collect();
+        let encoded = compress::<FastPFor256>(&data).unwrap();
+        let mut calls = 0;
+        let flow = FastPFor256::default()
+            .decode_with(&encoded, |block| {
+                calls += 1;
+                match block.iter().position(|&v| v == 300) {
+                    Some(i) => ControlFlow::Break(i),
+                    None => ControlFlow::Continue(()),
+                }
+            })
+            .unwrap();
+        assert_eq!(flow, ControlFlow::Break(44));
+        assert_eq!(calls, 2);
+
+        // A break in the remainder is returned as well.
+        let flow = FastPFor256::default()
+            .decode_with(&encoded, |block| {
+                if block.len() < 256 {
+                    ControlFlow::Break(block[0])
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .unwrap();
+        assert_eq!(flow, ControlFlow::Break(768));
+    }
+
+    #[test]
+    fn test_decode_with_break_skips_rest_of_page() {
+        let data = vec![5u32; 1024];
+        let mut encoded = compress::<FastPFor256>(&data).unwrap();
+        // Give the third block of the only page an invalid bit width: decoding
+        // fails if it ever gets that far.
+        let meta = 1 + encoded[1] as usize;
+        encoded[meta + 2] |= 0xFF;
+        decompress::<FastPFor256>(&encoded, None).unwrap_err();
+
+        let mut calls = 0;
+        let flow = FastPFor256::default()
+            .decode_with(&encoded, |block| {
+                calls += 1;
+                if calls == 2 {
+                    ControlFlow::Break(block[0])
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .unwrap();
+        assert_eq!(flow, ControlFlow::Break(5));
+        assert_eq!(calls, 2);
+
+        // Blocks split into lanes stop at the first break as well.
+        let mut codec = CompositeCodec::<FastPFor<256, u64>, VariableByte>::default();
+        let mut wide = Vec::new();
+        codec.encode(&[u64::MAX; 1024], &mut wide).unwrap();
+        let mut calls = 0;
+        let flow = codec
+            .decode_with(&wide, |_| {
+                calls += 1;
+                ControlFlow::Break(())
+            })
+            .unwrap();
+        assert!(flow.is_break());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_decode_with_propagates_errors() {
+        let data: Vec<u32> = (0..512).collect();
+        let mut encoded = compress::<FastPFor256>(&data).unwrap();
+        encoded.truncate(encoded.len() / 2);
+        FastPFor256::default()
+            .decode_with(&encoded, |_| ControlFlow::<()>::Continue(()))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_iter_bad_header_errors_once() {
+        let mut codec = FastPFor256::default();
+        let mut iter = codec.decode_iter(&[3, 0, 0]);
+        assert!(matches!(
+            iter.next(),
+            Some(Err(FastPForError::NotEnoughData))
+        ));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_iter_truncated_page_errors() {
+        let data: Vec<u32> = (0..512).collect();
+        let mut encoded = compress::<FastPFor256>(&data).unwrap();
+        encoded.truncate(encoded.len() / 2);
+        decompress::<FastPFor256>(&encoded, None).unwrap_err();
+        let mut codec = FastPFor256::default();
+        let results: Vec<_> = codec.decode_iter(&encoded).collect();
+        assert!(results.last().unwrap().is_err());
+        assert_eq!(results.iter().filter(|r| r.is_err()).count(), 1);
+    }
+}
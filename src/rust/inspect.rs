@@ -0,0 +1,314 @@
+//! Structural inspection of encoded `FastPFor` streams: per-page and per-block
+//! bit widths, exceptions and sizes.
+
+use alloc::vec::Vec;
+
+use crate::codec::le_bytes;
+use crate::helpers::{AsUsize, GetWithErr};
+use crate::rust::FastPFor;
+use crate::rust::integer_compression::fastpfor::sealed::BlockSize;
+use crate::{FastPForError, FastPForResult};
+
+/// Layout of one encoded `FastPFor` page, returned by [`FastPFor::inspect`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageInfo {
+    values: usize,
+    words: usize,
+    packed_words: usize,
+    metadata_bytes: usize,
+    exception_bitmap: u32,
+    exceptions: Vec<ExceptionWidth>,
+    blocks: Vec<BlockInfo>,
+}
+
+impl PageInfo {
+    /// Number of values in the page.
+    #[must_use]
+    pub fn values(&self) -> usize {
+        self.values
+    }
+
+    /// Total size of the page in `u32` words, including the checksum prefix if any.
+    #[must_use]
+    pub fn words(&self) -> usize {
+        self.words
+    }
+
+    /// Words of bit-packed regular values, summed over the page's blocks.
+    #[must_use]
+    pub fn packed_words(&self) -> usize {
+        self.packed_words
+    }
+
+    /// Size of the per-block metadata (bit widths and exception positions) in bytes.
+    #[must_use]
+    pub fn metadata_bytes(&self) -> usize {
+        self.metadata_bytes
+    }
+
+    /// Bitmap of the exception widths stored in the page: bit `k - 1` is set
+    /// if exceptions of `k` extra bits are present.
+    #[must_use]
+    pub fn exception_bitmap(&self) -> u32 {
+        self.exception_bitmap
+    }
+
+    /// The packed exception sections, one per width in the bitmap, by increasing width.
+    #[must_use]
+    pub fn exceptions(&self) -> &[ExceptionWidth] {
+        &self.exceptions
+    }
+
+    /// Details of each block in the page, in order.
+    #[must_use]
+    pub fn blocks(&self) -> &[BlockInfo] {
+        &self.blocks
+    }
+}
+
+/// One packed exception section of a page: the high bits of all exceptions
+/// that need `width` extra bits.
+///
+/// Exceptions that need a single extra bit are not stored in a section; their
+/// position alone restores the bit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExceptionWidth {
+    width: u8,
+    count: u32,
+}
+
+impl ExceptionWidth {
+    /// Number of extra bits per exception, `2..=32`.
+    #[must_use]
+    pub fn width(self) -> u8 {
+        self.width
+    }
+
+    /// Number of exceptions in the section.
+    #[must_use]
+    pub fn count(self) -> u32 {
+        self.count
+    }
+
+    /// Size of the section in `u32` words, excluding its count word.
+    #[must_use]
+    pub fn words(self) -> usize {
+        (self.count.as_usize() * usize::from(self.width)).div_ceil(32)
+    }
+}
+
+/// Bit widths and exceptions of one encoded block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockInfo {
+    optimal_bits: u8,
+    max_bits: u8,
+    exception_count: u8,
+}
+
+impl BlockInfo {
+    /// Bit width the block's values are packed with.
+    #[must_use]
+    pub fn optimal_bits(self) -> u8 {
+        self.optimal_bits
+    }
+
+    /// Bit width of the block's largest value; equal to
+    /// [`optimal_bits`](Self::optimal_bits) when there are no exceptions.
+    #[must_use]
+    pub fn max_bits(self) -> u8 {
+        self.max_bits
+    }
+
+    /// Number of values that do not fit in `optimal_bits`.
+    #[must_use]
+    pub fn exception_count(self) -> u8 {
+        self.exception_count
+    }
+}
+
+impl<const N: usize> FastPFor<N>
+where
+    [u32; N]: BlockSize,
+{
+    /// Describes the pages of a stream written by
+    /// [`encode_blocks`](crate::BlockCodec::encode_blocks), without decoding it.
+    ///
+    /// The stream is first checked with [`validate`](Self::validate). Words
+    /// after the last page are ignored, so the stream of a
+    /// [`CompositeCodec`](crate::CompositeCodec) can be passed as is; its tail
+    /// is not described.
+    ///
+    /// ```
+    /// use fastpfor::{AnyLenCodec, FastPFor256, FastPForBlock256};
+    ///
+    /// let data: Vec<u32> = (0..1024).map(|i| if i % 100 == 0 { 1 << 20 } else { i % 8 }).collect();
+    /// let mut encoded = Vec::new();
+    /// FastPFor256::default().encode(&data, &mut encoded).unwrap();
+    ///
+    /// let pages = FastPForBlock256::default().inspect(&encoded).unwrap();
+    /// let block = pages[0].blocks()[0];
+    /// assert_eq!((block.optimal_bits(), block.max_bits()), (3, 21));
+    /// assert_eq!(block.exception_count(), 3);
+    /// ```
+    pub fn inspect(&mut self, input: &[u32]) -> FastPForResult<Vec<PageInfo>> {
+        let info = self.validate(input)?;
+        let checksums = self.config().checksums();
+        let page_values = self.values_per_page();
+        let mut pages = Vec::with_capacity(info.page_count());
+        let mut pos = 1;
+        let mut remaining = info.value_count();
+        while remaining > 0 {
+            let values = page_values.min(remaining);
+            let page = inspect_page::<N>(input, pos, values, checksums)?;
+            pos += page.words;
+            remaining -= values;
+            pages.push(page);
+        }
+        Ok(pages)
+    }
+}
+
+/// Parses the page at `input[start..]`, which must already be validated.
+fn inspect_page<const N: usize>(
+    input: &[u32],
+    start: usize,
+    values: usize,
+    checksums: bool,
+) -> FastPForResult<PageInfo> {
+    let header = if checksums { start + 2 } else { start };
+    let where_meta = input.get_val(header)?.as_usize();
+    let meta = header + where_meta;
+    let metadata_bytes = input.get_val(meta)?.as_usize();
+    let meta_words = metadata_bytes.div_ceil(4);
+    let meta_range = meta + 1..meta + 1 + meta_words;
+    let bytes = le_bytes(input.get(meta_range).ok_or(FastPForError::NotEnoughData)?);
+    let bytes: &[u8] = &bytes;
+    let exception_bitmap = input.get_val(meta + 1 + meta_words)?;
+
+    let mut pos = meta + 2 + meta_words;
+    let mut exceptions = Vec::new();
+    for width in 2..=32u8 {
+        if exception_bitmap & (1 << (width - 1)) != 0 {
+            let section = ExceptionWidth {
+                width,
+                count: input.get_val(pos)?,
+            };
+            pos += 1 + section.words();
+            exceptions.push(section);
+        }
+    }
+
+    let mut blocks = Vec::with_capacity(values / N);
+    let mut byte_pos = 0;
+    for _ in 0..values / N {
+        let optimal_bits = bytes.get_val(byte_pos)?;
+        let exception_count = bytes.get_val(byte_pos + 1)?;
+        byte_pos += 2;
+        let max_bits = if exception_count > 0 {
+            let max_bits = bytes.get_val(byte_pos)?;
+            byte_pos += 1 + usize::from(exception_count);
+            max_bits
+        } else {
+            optimal_bits
+        };
+        blocks.push(BlockInfo {
+            optimal_bits,
+            max_bits,
+            exception_count,
+        });
+    }
+
+    Ok(PageInfo {
+        values,
+        words: pos - start,
+        packed_words: where_meta - 1,
+        metadata_bytes,
+        exception_bitmap,
+        exceptions,
+        blocks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust::{FastPForBlock128, FastPForConfig};
+    use crate::{BlockCodec, slice_to_blocks};
+
+    fn encode(codec: &mut FastPForBlock128, data: &[u32]) -> Vec<u32> {
+        let mut encoded = Vec::new();
+        codec
+            .encode_blocks(slice_to_blocks::<FastPForBlock128>(data).0, &mut encoded)
+            .unwrap();
+        encoded
+    }
+
+    #[test]
+    fn test_page_sizes_add_up() {
+        for checksums in [false, true] {
+            let config = FastPForConfig::new(256).unwrap().with_checksums(checksums);
+            let mut codec = FastPForBlock128::from_config(config);
+            let data: Vec<u32> = (0..640u32)
+                .map(|i| i.wrapping_mul(2_654_435_761) >> (i % 32))
+                .collect();
+            let encoded = encode(&mut codec, &data);
+            let pages = codec.inspect(&encoded).unwrap();
+
+            assert_eq!(pages.len(), 3);
+            assert_eq!(
+                pages.iter().map(PageInfo::values).collect::<Vec<_>>(),
+                [256, 256, 128]
+            );
+            assert_eq!(
+                1 + pages.iter().map(PageInfo::words).sum::<usize>(),
+                encoded.len()
+            );
+            for page in &pages {
+                assert_eq!(page.blocks().len(), page.values() / 128);
+                let packed: usize = page
+                    .blocks()
+                    .iter()
+                    .map(|b| usize::from(b.optimal_bits()) * 4)
+                    .sum();
+                assert_eq!(page.packed_words(), packed);
+                let widths = page
+                    .exceptions()
+                    .iter()
+                    .fold(0, |bitmap, e| bitmap | 1 << (e.width() - 1));
+                assert_eq!(page.exception_bitmap(), widths);
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_details() {
+        let mut data = vec![5u32; 128];
+        data[7] = 1 << 30;
+        data[8] = 1 << 30;
+        data.extend([12; 128]);
+        let pages = FastPForBlock128::default()
+            .inspect(&encode(&mut FastPForBlock128::default(), &data))
+            .unwrap();
+        let blocks = pages[0].blocks();
+        assert_eq!((blocks[0].optimal_bits(), blocks[0].max_bits()), (3, 31));
+        assert_eq!(blocks[0].exception_count(), 2);
+        assert_eq!(blocks[1].optimal_bits(), 4);
+        assert_eq!(blocks[1].exception_count(), 0);
+        assert_eq!(blocks[1].max_bits(), 4);
+
+        let exceptions = pages[0].exceptions();
+        assert_eq!(exceptions.len(), 1);
+        assert_eq!((exceptions[0].width(), exceptions[0].count()), (28, 2));
+        assert_eq!(exceptions[0].words(), 2);
+        assert_eq!(pages[0].metadata_bytes(), 2 + 1 + 2 + 2);
+    }
+
+    #[test]
+    fn test_empty_and_invalid_streams() {
+        let mut codec = FastPForBlock128::default();
+        assert!(codec.inspect(&[0]).unwrap().is_empty());
+        let mut encoded = encode(&mut FastPForBlock128::default(), &[9; 128]);
+        encoded.pop();
+        assert!(codec.inspect(&encoded).is_err());
+    }
+}
@@ -1,16 +1,20 @@
-use std::array;
-use std::cmp::min;
-use std::io::Cursor;
+use alloc::vec::Vec;
+use core::array;
+use core::cmp::min;
+use core::marker::PhantomData;
+use core::ops::{ControlFlow, Range};
 
 use bytemuck::cast_slice;
-use bytes::{Buf as _, BufMut as _, BytesMut};
 
+use crate::codec::encode_with_bound;
 use crate::helpers::{AsUsize, GetWithErr, bits, greatest_multiple};
-use crate::rust::cursor::IncrementCursor;
+use crate::rust::crc32c::crc32c_words;
+use crate::rust::cursor::Cursor;
 use crate::rust::integer_compression::{bitpacking, bitunpacking};
-use crate::{BlockCodec, FastPForError, FastPForResult};
+use crate::rust::stream_info::StreamInfo;
+use crate::{BlockCodec, Element, FastPForError, FastPForResult};
 
-mod sealed {
+pub(crate) mod sealed {
     /// Sealed marker trait: only `[u32; 128]` and `[u32; 256]` are valid `FastPFor` block arrays.
     ///
     /// This is intentionally private so that users cannot implement it for other sizes,
@@ -34,8 +38,9 @@ pub type FastPForBlock256 = FastPFor<256>;
 
 /// Fast Patched Frame-of-Reference ([FastPFOR](https://github.com/lemire/FastPFor)) codec.
 ///
-/// `N` is the block size (128 or 256 values per block). This struct implements
-/// [`BlockCodec`] with `Block = [u32; N]`, giving compile-time guarantees that
+/// `N` is the block size (128 or 256 values per block) and `T` is the
+/// [`Element`] type of the values (`u32` by default). This struct implements
+/// [`BlockCodec<T>`] with `Block = [T; N]`, giving compile-time guarantees that
 /// only correctly-sized blocks are accepted.
 ///
 /// Use [`FastPForBlock128`] or [`FastPForBlock256`] as convenient type aliases.
@@ -50,14 +55,75 @@ pub type FastPForBlock256 = FastPFor<256>;
 /// let mut codec = FastPFor256::default();
 /// codec.encode(&data, &mut out).unwrap();
 /// ```
+///
+/// For other element types, each page is widened into an internal page-sized
+/// buffer for packing and narrowed back on decode, so memory use stays bounded
+/// by the page size rather than the input length. `u64` / `i64` blocks are
+/// packed as two `u32` blocks (low and high halves), so values that fit in
+/// 32 bits cost almost nothing extra:
+///
+/// ```
+/// # use fastpfor::{AnyLenCodec, CompositeCodec, FastPFor, VariableByte};
+/// let data: Vec<u16> = (0..1000).collect();
+/// let mut codec = CompositeCodec::<FastPFor<256, u16>, VariableByte>::default();
+///
+/// let mut encoded = Vec::new();
+/// codec.encode(&data, &mut encoded).unwrap();
+///
+/// let mut decoded = Vec::new();
+/// codec.decode(&encoded, &mut decoded, None).unwrap();
+/// assert_eq!(decoded, data);
+/// ```
 #[derive(Debug)]
-pub struct FastPFor<const N: usize> {
+pub struct FastPFor<const N: usize, T: Element = u32> {
+    config: FastPForConfig<N, T>,
+    scratch: FastPForScratch,
+}
+
+/// Stateless configuration of a [`FastPFor`] codec: block size, element type,
+/// page size and whether pages carry checksums.
+///
+/// It is `Copy` and `Sync`, so a single value can be shared by many threads.
+/// Each call borrows the buffers it needs from a [`FastPForScratch`], which can
+/// be kept per thread or pooled (e.g. in a [`CodecPool`](crate::CodecPool)),
+/// independently of the codec configurations that use it.
+///
+/// ```
+/// use fastpfor::{FastPForConfig, FastPForScratch, slice_to_blocks};
+///
+/// let config = FastPForConfig::<256>::default();
+/// let mut scratch = FastPForScratch::default();
+///
+/// let data: Vec<u32> = (0..512).collect();
+/// let (blocks, _) = slice_to_blocks::<fastpfor::FastPForBlock256>(&data);
+/// let mut encoded = Vec::new();
+/// config.encode_blocks(&mut scratch, blocks, &mut encoded).unwrap();
+///
+/// let mut decoded = Vec::new();
+/// config.decode_blocks(&mut scratch, &encoded, None, &mut decoded).unwrap();
+/// assert_eq!(decoded, data);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FastPForConfig<const N: usize, T: Element = u32> {
+    /// Maximum integers per page
+    page_size: u32,
+    /// Prefix every page with its length and CRC-32C
+    checksums: bool,
+    _element: PhantomData<T>,
+}
+
+/// Mutable scratch space used by [`FastPFor`] while encoding and decoding.
+///
+/// Holds the exception buffers, the metadata buffer and the per-block
+/// bit-width statistics. None of it carries over between calls, so one scratch
+/// can serve any number of [`FastPForConfig`]s, whatever their block size,
+/// element type or page size. Buffers grow on first use and are then reused.
+#[derive(Debug)]
+pub struct FastPForScratch {
     /// Exception values indexed by bit width difference
     exception_buffers: [Vec<u32>; 33],
     /// Metadata buffer for encoding/decoding
-    bytes_container: BytesMut,
-    /// Maximum integers per page
-    page_size: u32,
+    bytes_container: Vec<u8>,
     /// Position trackers for exception arrays
     data_pointers: [usize; 33],
     /// Frequency count for each bit width:
@@ -69,9 +135,35 @@ pub struct FastPFor<const N: usize> {
     exception_count: u8,
     /// Maximum bit width required for any value in the block
     max_bits: u8,
+    /// `u32` lanes of the current page when `T` is not stored as `u32` words
+    page_buffer: Vec<u32>,
+}
+
+impl Default for FastPForScratch {
+    fn default() -> Self {
+        Self {
+            exception_buffers: array::from_fn(|_| Vec::new()),
+            bytes_container: Vec::new(),
+            data_pointers: [0; 33],
+            freqs: [0; 33],
+            optimal_bits: 0,
+            exception_count: 0,
+            max_bits: 0,
+            page_buffer: Vec::new(),
+        }
+    }
+}
+
+impl<const N: usize, T: Element> Default for FastPFor<N, T>
+where
+    [u32; N]: sealed::BlockSize,
+{
+    fn default() -> Self {
+        Self::from_config(FastPForConfig::default())
+    }
 }
 
-impl<const N: usize> Default for FastPFor<N>
+impl<const N: usize, T: Element> Default for FastPForConfig<N, T>
 where
     [u32; N]: sealed::BlockSize,
 {
@@ -81,10 +173,10 @@ where
     }
 }
 
-impl<const N: usize> FastPFor<N> {
-    /// Creates a new `FastPForBlock` with a codec with the given page size.
+impl<const N: usize, T: Element> FastPForConfig<N, T> {
+    /// Creates a configuration with the given page size.
     ///
-    /// Returns an error if `page_size` is not a multiple of 128.
+    /// Returns an error if `page_size` is not a multiple of the block size `N`.
     /// Use [`Default`] for the default page size.
     pub fn new(page_size: u32) -> FastPForResult<Self> {
         if page_size % N as u32 != 0 {
@@ -94,19 +186,139 @@ impl<const N: usize> FastPFor<N> {
             });
         }
         Ok(Self {
-            bytes_container: BytesMut::with_capacity(
-                (3 * page_size / N as u32 + page_size) as usize,
-            ),
             page_size,
-            exception_buffers: array::from_fn(|_| Vec::new()),
-            data_pointers: [0; 33],
-            freqs: [0; 33],
-            optimal_bits: 0,
-            exception_count: 0,
-            max_bits: 0,
+            checksums: false,
+            _element: PhantomData,
         })
     }
 
+    /// Maximum number of `u32` words per page.
+    #[must_use]
+    pub const fn page_size(self) -> u32 {
+        self.page_size
+    }
+
+    /// Enables or disables per-page checksums.
+    ///
+    /// With checksums, every encoded page is prefixed by two words: its length
+    /// in words and the CRC-32C of its words. The decoder verifies each page
+    /// before unpacking it and returns [`FastPForError::ChecksumMismatch`] with
+    /// the page index on corruption. This changes the wire format: a stream
+    /// must be decoded with the same setting, and is no longer readable by the
+    /// C++ library. The length header and the tail of a
+    /// [`CompositeCodec`](crate::CompositeCodec) are not covered.
+    ///
+    /// ```
+    /// use fastpfor::{AnyLenCodec, CompositeCodec, FastPFor256, FastPForBlock256, FastPForConfig};
+    /// use fastpfor::{FastPForError, VariableByte};
+    ///
+    /// let config = FastPForConfig::default().with_checksums(true);
+    /// let mut codec = FastPFor256::new(FastPForBlock256::from_config(config), VariableByte::new());
+    ///
+    /// let data: Vec<u32> = (0..1000).collect();
+    /// let mut encoded = Vec::new();
+    /// codec.encode(&data, &mut encoded).unwrap();
+    ///
+    /// encoded[10] ^= 1 << 7;
+    /// let err = codec.decode(&encoded, &mut Vec::new(), None).unwrap_err();
+    /// assert!(matches!(err, FastPForError::ChecksumMismatch { page: 0 }));
+    /// ```
+    #[must_use]
+    pub const fn with_checksums(mut self, enabled: bool) -> Self {
+        self.checksums = enabled;
+        self
+    }
+
+    /// Whether pages carry checksums, see [`with_checksums`](Self::with_checksums).
+    #[must_use]
+    pub const fn checksums(self) -> bool {
+        self.checksums
+    }
+
+    /// Runs `f` on a [`FastPFor`] codec with this configuration that borrows
+    /// `scratch` for the duration of the call.
+    ///
+    /// Gives access to the full codec API, e.g. [`BlockCodec`] methods or
+    /// [`FastPFor::decode_with`], without owning any scratch space.
+    pub fn with_scratch<R>(
+        self,
+        scratch: &mut FastPForScratch,
+        f: impl FnOnce(&mut FastPFor<N, T>) -> R,
+    ) -> R {
+        let mut codec = FastPFor {
+            config: self,
+            scratch: core::mem::take(scratch),
+        };
+        let result = f(&mut codec);
+        *scratch = codec.scratch;
+        result
+    }
+}
+
+impl<const N: usize, T: Element> FastPForConfig<N, T>
+where
+    [u32; N]: sealed::BlockSize,
+{
+    /// Like [`BlockCodec::encode_blocks`], using `scratch` for working memory.
+    pub fn encode_blocks(
+        self,
+        scratch: &mut FastPForScratch,
+        blocks: &[[T; N]],
+        out: &mut Vec<u32>,
+    ) -> FastPForResult<()> {
+        self.with_scratch(scratch, |codec| codec.encode_blocks(blocks, out))
+    }
+
+    /// Like [`BlockCodec::decode_blocks`], using `scratch` for working memory.
+    pub fn decode_blocks(
+        self,
+        scratch: &mut FastPForScratch,
+        input: &[u32],
+        expected_len: Option<u32>,
+        out: &mut Vec<T>,
+    ) -> FastPForResult<usize> {
+        self.with_scratch(scratch, |codec| {
+            codec.decode_blocks(input, expected_len, out)
+        })
+    }
+}
+
+impl<const N: usize, T: Element> FastPFor<N, T> {
+    /// Creates a new `FastPForBlock` with a codec with the given page size.
+    ///
+    /// Returns an error if `page_size` is not a multiple of 128.
+    /// Use [`Default`] for the default page size.
+    pub fn new(page_size: u32) -> FastPForResult<Self> {
+        FastPForConfig::new(page_size).map(Self::from_config)
+    }
+
+    /// Creates a codec with the given configuration and its own scratch space.
+    #[must_use]
+    pub fn from_config(config: FastPForConfig<N, T>) -> Self {
+        Self {
+            config,
+            scratch: FastPForScratch::default(),
+        }
+    }
+
+    /// Returns the codec's configuration, without its scratch space.
+    #[must_use]
+    pub const fn config(&self) -> FastPForConfig<N, T> {
+        self.config
+    }
+
+    /// Number of values in one block (`N`).
+    ///
+    /// Shadows [`BlockCodec::size`] so that `FastPForBlock256::size()` needs no
+    /// element type annotation.
+    #[inline]
+    #[must_use]
+    pub const fn size() -> usize {
+        N
+    }
+
+    /// Encodes `input_length` values (rounded down to whole blocks) page by page.
+    /// If `page_starts` is given, the output position of each page is appended to it.
     fn compress_blocks(
         &mut self,
         input: &[u32],
@@ -114,30 +326,133 @@ impl<const N: usize> FastPFor<N> {
         input_offset: &mut Cursor<u32>,
         output: &mut [u32],
         output_offset: &mut Cursor<u32>,
+        mut page_starts: Option<&mut Vec<u32>>,
     ) {
         let inlength = greatest_multiple(input_length, N as u32);
         let final_inpos = input_offset.position() as u32 + inlength;
         while input_offset.position() as u32 != final_inpos {
-            let this_size = min(self.page_size, final_inpos - input_offset.position() as u32);
+            let this_size = min(
+                self.config.page_size,
+                final_inpos - input_offset.position() as u32,
+            );
+            if let Some(starts) = page_starts.as_deref_mut() {
+                starts.push(output_offset.position() as u32);
+            }
             self.encode_page(input, this_size, input_offset, output, output_offset);
         }
     }
 
+    /// Decodes `inlength` values (rounded down to whole blocks) page by page,
+    /// passing each decoded block to `emit`. `first_page` is the index of the
+    /// first page in the stream, for checksum errors.
+    ///
+    /// Stops as soon as `emit` breaks, leaving `input_offset` inside the page.
     fn decode_headless_blocks(
         &mut self,
         input: &[u32],
         inlength: u32,
         input_offset: &mut Cursor<u32>,
+        first_page: usize,
+        emit: &mut impl FnMut(&[u32; N]) -> FastPForResult<ControlFlow<()>>,
+    ) -> FastPForResult<ControlFlow<()>> {
+        let mut remaining = greatest_multiple(inlength, N as u32);
+        let mut page = first_page;
+        while remaining != 0 {
+            let this_size = min(self.config.page_size, remaining);
+            if self
+                .decode_page(input, input_offset, this_size, page, emit)?
+                .is_break()
+            {
+                return Ok(ControlFlow::Break(()));
+            }
+            remaining -= this_size;
+            page += 1;
+        }
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Number of `T` values per page, so that a page split into `u32` lanes
+    /// holds at most `page_size` words (but always at least one block).
+    pub(crate) fn values_per_page(&self) -> usize {
+        (self.config.page_size as usize / T::LANES / N * N).max(N)
+    }
+
+    /// Like [`compress_blocks`](Self::compress_blocks), for elements that are not
+    /// stored as `u32` words directly.
+    ///
+    /// Each page is split into `page_buffer` just before it is packed: every block
+    /// of `N` values becomes `T::LANES` consecutive blocks of `u32` words, low lane
+    /// first, so that each lane picks its own bit width.
+    fn compress_lanes(
+        &mut self,
+        input: &[T],
+        page_buffer: &mut Vec<u32>,
         output: &mut [u32],
         output_offset: &mut Cursor<u32>,
-    ) -> FastPForResult<()> {
-        let mynvalue = greatest_multiple(inlength, N as u32);
-        let final_out = output_offset.position() as u32 + mynvalue;
-        while output_offset.position() as u32 != final_out {
-            let this_size = min(self.page_size, final_out - output_offset.position() as u32);
-            self.decode_page(input, input_offset, output, output_offset, this_size)?;
+    ) {
+        for page in input.chunks(self.values_per_page()) {
+            page_buffer.clear();
+            for block in page.chunks_exact(N) {
+                for lane in 0..T::LANES {
+                    page_buffer.extend(block.iter().map(|&v| v.lane(lane)));
+                }
+            }
+            self.encode_page(
+                page_buffer,
+                page_buffer.len() as u32,
+                &mut Cursor::new(0),
+                output,
+                output_offset,
+            );
         }
-        Ok(())
+    }
+
+    /// Like [`decode_headless_blocks`](Self::decode_headless_blocks), for elements
+    /// that are not stored as `u32` words directly.
+    ///
+    /// Each page is unpacked into `page_buffer`, and its lanes are then joined
+    /// back into blocks of `N` values for `emit`.
+    fn decode_lanes(
+        &mut self,
+        input: &[u32],
+        input_offset: &mut Cursor<u32>,
+        page_buffer: &mut Vec<u32>,
+        n_values: usize,
+        first_page: usize,
+        emit: &mut impl FnMut(&[T]) -> FastPForResult<ControlFlow<()>>,
+    ) -> FastPForResult<ControlFlow<()>> {
+        let mut values = [T::default(); N];
+        let mut remaining = n_values;
+        let mut page = first_page;
+        while remaining != 0 {
+            let page_values = remaining.min(self.values_per_page());
+            page_buffer.clear();
+            // The callback only buffers the page, so it never breaks.
+            let _ = self.decode_page(
+                input,
+                input_offset,
+                (page_values * T::LANES) as u32,
+                page,
+                &mut |block| {
+                    page_buffer.extend_from_slice(block);
+                    Ok(ControlFlow::Continue(()))
+                },
+            )?;
+            for words in page_buffer.chunks_exact(N * T::LANES) {
+                for (i, dst) in values.iter_mut().enumerate() {
+                    let value = (0..T::LANES).fold(0, |acc, lane| {
+                        acc | u64::from(words[lane * N + i]) << (32 * lane)
+                    });
+                    *dst = T::from_u64(value).ok_or(FastPForError::ValueOutOfRange(value))?;
+                }
+                if emit(&values)?.is_break() {
+                    return Ok(ControlFlow::Break(()));
+                }
+            }
+            remaining -= page_values;
+            page += 1;
+        }
+        Ok(ControlFlow::Continue(()))
     }
 
     /// Encodes a page using optimal bit width per block.
@@ -159,35 +474,56 @@ impl<const N: usize> FastPFor<N> {
         output: &mut [u32],
         output_offset: &mut Cursor<u32>,
     ) {
+        let checksum_pos = output_offset.position() as usize;
+        if self.config.checksums {
+            output_offset.add(2);
+        }
         let header_pos = output_offset.position() as usize;
         output_offset.increment();
-        let mut tmp_output_offset = output_offset.position() as u32;
 
         // Data pointers to 0
-        self.data_pointers.fill(0);
-        self.bytes_container.clear();
+        self.scratch.data_pointers.fill(0);
+        self.scratch.bytes_container.clear();
 
+        self.pack_blocks(input, this_size, input_offset, output, output_offset);
+        self.finish_page(checksum_pos, header_pos, output, output_offset);
+    }
+
+    /// Bit-packs `this_size` values (whole blocks) at `output_offset`, adding
+    /// their metadata bytes and exceptions to those already in the scratch.
+    fn pack_blocks(
+        &mut self,
+        input: &[u32],
+        this_size: u32,
+        input_offset: &mut Cursor<u32>,
+        output: &mut [u32],
+        output_offset: &mut Cursor<u32>,
+    ) {
+        let mut tmp_output_offset = output_offset.position() as u32;
         let mut tmp_input_offset = input_offset.position() as u32;
         let final_input_offset = tmp_input_offset + this_size - N as u32;
         while tmp_input_offset <= final_input_offset {
             self.best_bit_from_data(input, tmp_input_offset);
-            self.bytes_container.put_u8(self.optimal_bits);
-            self.bytes_container.put_u8(self.exception_count);
-            if self.exception_count > 0 {
-                self.bytes_container.put_u8(self.max_bits);
-                let index = usize::from(self.max_bits - self.optimal_bits);
-                let needed = self.data_pointers[index] + usize::from(self.exception_count);
-                if needed > self.exception_buffers[index].len() {
+            self.scratch.bytes_container.push(self.scratch.optimal_bits);
+            self.scratch
+                .bytes_container
+                .push(self.scratch.exception_count);
+            if self.scratch.exception_count > 0 {
+                self.scratch.bytes_container.push(self.scratch.max_bits);
+                let index = usize::from(self.scratch.max_bits - self.scratch.optimal_bits);
+                let needed =
+                    self.scratch.data_pointers[index] + usize::from(self.scratch.exception_count);
+                if needed > self.scratch.exception_buffers[index].len() {
                     // Grow to the next multiple of 32 above 2×needed, to amortize resizes.
                     let new_cap = needed.saturating_mul(2).next_multiple_of(32);
-                    self.exception_buffers[index].resize(new_cap, 0);
+                    self.scratch.exception_buffers[index].resize(new_cap, 0);
                 }
                 for k in 0..N as u32 {
-                    if (input[(k + tmp_input_offset) as usize] >> self.optimal_bits) != 0 {
-                        self.bytes_container.put_u8(k as u8);
-                        self.exception_buffers[index][self.data_pointers[index]] =
-                            input[(k + tmp_input_offset) as usize] >> self.optimal_bits;
-                        self.data_pointers[index] += 1;
+                    if (input[(k + tmp_input_offset) as usize] >> self.scratch.optimal_bits) != 0 {
+                        self.scratch.bytes_container.push(k as u8);
+                        self.scratch.exception_buffers[index][self.scratch.data_pointers[index]] =
+                            input[(k + tmp_input_offset) as usize] >> self.scratch.optimal_bits;
+                        self.scratch.data_pointers[index] += 1;
                     }
                 }
             }
@@ -197,30 +533,49 @@ impl<const N: usize> FastPFor<N> {
                     (tmp_input_offset + k) as usize,
                     output,
                     tmp_output_offset as usize,
-                    self.optimal_bits,
+                    self.scratch.optimal_bits,
                 );
-                tmp_output_offset += u32::from(self.optimal_bits);
+                tmp_output_offset += u32::from(self.scratch.optimal_bits);
             }
             tmp_input_offset += N as u32;
         }
         input_offset.set_position(u64::from(tmp_input_offset));
+        output_offset.set_position(u64::from(tmp_output_offset));
+    }
+
+    /// Ends the page whose header is at `header_pos` and whose packed blocks end
+    /// at `output_offset`: writes the header, the metadata bytes and the
+    /// exceptions from the scratch, then the checksum prefix if enabled.
+    fn finish_page(
+        &mut self,
+        checksum_pos: usize,
+        header_pos: usize,
+        output: &mut [u32],
+        output_offset: &mut Cursor<u32>,
+    ) {
+        let mut tmp_output_offset = output_offset.position() as u32;
         output[header_pos] = tmp_output_offset - header_pos as u32;
-        let byte_size = self.bytes_container.len();
-        while (self.bytes_container.len() & 3) != 0 {
-            self.bytes_container.put_u8(0);
+        let byte_size = self.scratch.bytes_container.len();
+        while (self.scratch.bytes_container.len() & 3) != 0 {
+            self.scratch.bytes_container.push(0);
         }
         // Output should have 3 position as 4
         output[tmp_output_offset as usize] = byte_size as u32;
         tmp_output_offset += 1;
-        let how_many_ints = self.bytes_container.len() / 4;
-        // Match C++ memcpy: copy metadata bytes as u32s in one shot (native byte order).
-        let meta_u32s: &[u32] = cast_slice(self.bytes_container.chunk());
-        output[tmp_output_offset as usize..][..how_many_ints]
-            .copy_from_slice(&meta_u32s[..how_many_ints]);
+        let how_many_ints = self.scratch.bytes_container.len() / 4;
+        // Metadata bytes are packed into words little-endian, whatever the host's
+        // byte order. On little-endian hosts this matches the C++ `memcpy`.
+        let meta_out = &mut output[tmp_output_offset as usize..][..how_many_ints];
+        for (word, bytes) in meta_out
+            .iter_mut()
+            .zip(self.scratch.bytes_container.chunks_exact(4))
+        {
+            *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
         tmp_output_offset += how_many_ints as u32;
         let mut bitmap = 0;
         for k in 2..=32 {
-            if self.data_pointers[k] != 0 {
+            if self.scratch.data_pointers[k] != 0 {
                 bitmap |= 1 << (k - 1);
             }
         }
@@ -228,13 +583,13 @@ impl<const N: usize> FastPFor<N> {
         tmp_output_offset += 1;
 
         for k in 2..=32 {
-            if self.data_pointers[k] != 0 {
-                output[tmp_output_offset as usize] = self.data_pointers[k] as u32;
+            if self.scratch.data_pointers[k] != 0 {
+                output[tmp_output_offset as usize] = self.scratch.data_pointers[k] as u32;
                 tmp_output_offset += 1;
                 let mut j = 0;
-                while j < self.data_pointers[k] {
+                while j < self.scratch.data_pointers[k] {
                     bitpacking::fast_pack(
-                        &self.exception_buffers[k],
+                        &self.scratch.exception_buffers[k],
                         j,
                         output,
                         tmp_output_offset as usize,
@@ -245,39 +600,98 @@ impl<const N: usize> FastPFor<N> {
                 }
 
                 // Overflow adjustment
-                let overflow = j as u32 - self.data_pointers[k] as u32;
+                let overflow = j as u32 - self.scratch.data_pointers[k] as u32;
                 tmp_output_offset -= (overflow * k as u32) / 32;
             }
         }
         output_offset.set_position(u64::from(tmp_output_offset));
+        if self.config.checksums {
+            let crc = crc32c_words(&output[header_pos..tmp_output_offset as usize]);
+            output[checksum_pos] = tmp_output_offset - header_pos as u32;
+            output[checksum_pos + 1] = crc;
+        }
+    }
+
+    /// Counts, per exception bit width, how many exceptions the first `blocks_end`
+    /// blocks of a page use, by scanning their metadata from `byte_pos`.
+    ///
+    /// Returns `u32::MAX` for every width when all `run_end` blocks are decoded,
+    /// so that the whole page's exceptions are unpacked without a scan.
+    fn exceptions_needed(
+        input_bytes: LeBytes<'_>,
+        mut byte_pos: usize,
+        blocks_end: u32,
+        run_end: u32,
+    ) -> FastPForResult<[u32; 33]> {
+        if blocks_end >= run_end {
+            return Ok([u32::MAX; 33]);
+        }
+        let mut needed = [0; 33];
+        for _ in 0..blocks_end {
+            let bits = input_bytes.get_val(byte_pos)?;
+            let num_exceptions = input_bytes.get_val(byte_pos + 1)?;
+            byte_pos += 2;
+            if num_exceptions > 0 {
+                let maxbits: u8 = input_bytes.get_val(byte_pos)?;
+                byte_pos += 1 + usize::from(num_exceptions);
+                // Invalid widths are rejected while decoding the blocks.
+                if let Some(count) = needed.get_mut(usize::from(maxbits.wrapping_sub(bits))) {
+                    *count += u32::from(num_exceptions);
+                }
+            }
+        }
+        Ok(needed)
+    }
+
+    /// Checks the length and CRC-32C words in front of a checksummed page and
+    /// advances `input_offset` past them. Returns the position just after the page.
+    fn verify_page_checksum(
+        input: &[u32],
+        input_offset: &mut Cursor<u32>,
+        page: usize,
+    ) -> FastPForResult<u32> {
+        let pos =
+            u32::try_from(input_offset.position()).map_err(|_| FastPForError::NotEnoughData)?;
+        let len = input.get_val(pos)?;
+        let expected = input.get_val(pos + 1)?;
+        let start = pos + 2;
+        let end = start.checked_add(len).ok_or(FastPForError::NotEnoughData)?;
+        let words = input
+            .get(start as usize..end as usize)
+            .ok_or(FastPForError::NotEnoughData)?;
+        if crc32c_words(words) != expected {
+            return Err(FastPForError::ChecksumMismatch { page });
+        }
+        input_offset.set_position(u64::from(start));
+        Ok(end)
     }
 
     /// Computes optimal bit width minimizing total storage cost.
     ///
     /// Analyzes frequency distribution to balance regular value bits against exception overhead.
     fn best_bit_from_data(&mut self, input: &[u32], pos: u32) {
-        self.freqs.fill(0);
+        self.scratch.freqs.fill(0);
         let k_end = min(pos + N as u32, input.len() as u32);
         for k in pos..k_end {
-            self.freqs[bits(input[k as usize])] += 1;
+            self.scratch.freqs[bits(input[k as usize])] += 1;
         }
 
-        self.optimal_bits = 32;
-        while self.freqs[self.optimal_bits as usize] == 0 {
-            self.optimal_bits -= 1;
+        self.scratch.optimal_bits = 32;
+        while self.scratch.freqs[self.scratch.optimal_bits as usize] == 0 {
+            self.scratch.optimal_bits -= 1;
         }
-        self.max_bits = self.optimal_bits;
+        self.scratch.max_bits = self.scratch.optimal_bits;
 
-        let mut best_cost = u32::from(self.optimal_bits) * N as u32;
+        let mut best_cost = u32::from(self.scratch.optimal_bits) * N as u32;
         let mut num_exceptions: u32 = 0;
-        self.exception_count = 0;
+        self.scratch.exception_count = 0;
 
-        for bits in (0..self.optimal_bits).rev() {
-            num_exceptions += self.freqs[bits as usize + 1];
+        for bits in (0..self.scratch.optimal_bits).rev() {
+            num_exceptions += self.scratch.freqs[bits as usize + 1];
             if num_exceptions == N as u32 {
                 break;
             }
-            let diff = u32::from(self.max_bits - bits);
+            let diff = u32::from(self.scratch.max_bits - bits);
             let mut cost = num_exceptions * OVERHEAD_OF_EACH_EXCEPT
                 + num_exceptions * diff
                 + u32::from(bits) * N as u32
@@ -287,8 +701,8 @@ impl<const N: usize> FastPFor<N> {
             }
             if cost < best_cost {
                 best_cost = cost;
-                self.optimal_bits = bits;
-                self.exception_count = num_exceptions as u8;
+                self.scratch.optimal_bits = bits;
+                self.scratch.exception_count = num_exceptions as u8;
             }
         }
     }
@@ -298,21 +712,51 @@ impl<const N: usize> FastPFor<N> {
     /// Reads header to locate exception data, loads exceptions by bit width,
     /// unpacks regular values per block, patches in exceptions by position.
     ///
+    /// Each block is unpacked into a stack buffer and handed to `emit` once it is
+    /// complete, so the caller's output is written exactly once and never needs
+    /// to be zero-filled up front.
+    ///
     /// # Arguments
     /// * `this_size` - Expected decompressed integer count
     /// * `input_offset` - Advanced by bytes read
-    /// * `output_offset` - Advanced by `this_size`
-    #[expect(clippy::too_many_lines)]
+    /// * `page` - Index of the page in the stream, reported on checksum mismatch
+    /// * `emit` - Called once per decoded block, `this_size / N` times unless it breaks
     fn decode_page(
         &mut self,
         input: &[u32],
         input_offset: &mut Cursor<u32>,
-        output: &mut [u32],
-        output_offset: &mut Cursor<u32>,
         this_size: u32,
-    ) -> FastPForResult<()> {
+        page: usize,
+        emit: &mut impl FnMut(&[u32; N]) -> FastPForResult<ControlFlow<()>>,
+    ) -> FastPForResult<ControlFlow<()>> {
+        self.decode_page_blocks(input, input_offset, this_size, page, 0..u32::MAX, emit)
+    }
+
+    /// Like [`decode_page`](Self::decode_page), but only emits the page's blocks
+    /// whose index is in `blocks`.
+    ///
+    /// Blocks before the range are skipped using their metadata alone, and the
+    /// exceptions of blocks after it are not unpacked. An empty range checks the
+    /// metadata of every block without unpacking anything. `input_offset` still
+    /// ends up at the end of the page, unless `emit` breaks: decoding then
+    /// stops at once and the rest of the page is left unchecked.
+    #[expect(clippy::too_many_lines)]
+    pub(crate) fn decode_page_blocks(
+        &mut self,
+        input: &[u32],
+        input_offset: &mut Cursor<u32>,
+        this_size: u32,
+        page: usize,
+        blocks: Range<u32>,
+        emit: &mut impl FnMut(&[u32; N]) -> FastPForResult<ControlFlow<()>>,
+    ) -> FastPForResult<ControlFlow<()>> {
         let n = u32::try_from(input.len())
             .map_err(|_| FastPForError::InvalidInputLength(input.len()))?;
+        let page_end = if self.config.checksums {
+            Some(Self::verify_page_checksum(input, input_offset, page)?)
+        } else {
+            None
+        };
 
         let init_pos =
             u32::try_from(input_offset.position()).map_err(|_| FastPForError::NotEnoughData)?;
@@ -325,16 +769,15 @@ impl<const N: usize> FastPFor<N> {
         inexcept = inexcept
             .checked_add(1)
             .ok_or(FastPForError::NotEnoughData)?;
-        // Point a byte cursor directly at the metadata region in `input`,
-        // mirrors C++ `const uint8_t *bytep = reinterpret_cast<const uint8_t *>(inexcept)`.
-        // The C++ encoder uses a raw `memcpy` of bytes into the u32 output (no endian
-        // conversion), and the decoder does a raw reinterpret_cast back -- both native byte
-        // order. `cast_slice` is the exact Rust equivalent: a safe, zero-copy native view.
-        let input_bytes: &[u8] = cast_slice(input);
+        // Point a byte cursor at the metadata region in `input`, like C++
+        // `const uint8_t *bytep = reinterpret_cast<const uint8_t *>(inexcept)`, but
+        // reading the bytes little-endian so that the format is host-independent.
+        let input_bytes = LeBytes(input);
         let mut byte_pos = (inexcept as usize)
             .checked_mul(4)
-            .filter(|&bp| bp <= input_bytes.len())
+            .filter(|&bp| bp <= input.len() * 4)
             .ok_or(FastPForError::NotEnoughData)?;
+        let metadata_end = byte_pos + bytesize.as_usize();
         let length = bytesize.div_ceil(4);
         inexcept = inexcept
             .checked_add(length)
@@ -345,40 +788,61 @@ impl<const N: usize> FastPFor<N> {
             .checked_add(1)
             .ok_or(FastPForError::NotEnoughData)?;
 
+        let run_end = this_size / N as u32;
+        let (skip_end, blocks_end, needed) = if blocks.is_empty() {
+            (run_end, run_end, [0; 33])
+        } else {
+            let blocks_end = blocks.end.min(run_end);
+            let needed = Self::exceptions_needed(input_bytes, byte_pos, blocks_end, run_end)?;
+            (blocks.start, blocks_end, needed)
+        };
+        let mut exception_sizes = [0u32; 33];
+
         for k in 2..=32 {
             if (bitmap & (1 << (k - 1))) != 0 {
                 let size = input.get_val(inexcept)?;
                 inexcept = inexcept
                     .checked_add(1)
                     .ok_or(FastPForError::NotEnoughData)?;
-                // Reject adversarial inputs: exceptions can't exceed the page size.
-                if size > self.page_size {
+                // Reject adversarial inputs: exceptions can't exceed the values in the page.
+                if size > this_size {
                     return Err(FastPForError::NotEnoughData);
                 }
+                exception_sizes[k as usize] = size;
                 // Ensure the buffer is large enough for `size` values, rounded up
                 // to the next group of 32 for the bitunpacking calls.
                 let rounded_up = size.next_multiple_of(32) as usize;
-                if self.exception_buffers[k as usize].len() < rounded_up {
-                    self.exception_buffers[k as usize].resize(rounded_up, 0);
+                if self.scratch.exception_buffers[k as usize].len() < rounded_up {
+                    self.scratch.exception_buffers[k as usize].resize(rounded_up, 0);
                 }
+                // Only unpack the groups of 32 that the decoded blocks reach.
+                let unpack = needed[k as usize]
+                    .checked_next_multiple_of(32)
+                    .map_or(size, |needed| size.min(needed));
+                let section_end =
+                    (u64::from(size) * u64::from(k)).div_ceil(32) + u64::from(inexcept);
+                let section_end = u32::try_from(section_end)
+                    .ok()
+                    .filter(|&end| end <= n)
+                    .ok_or(FastPForError::NotEnoughData)?;
                 let mut j: u32 = 0;
                 // Process full groups directly from input
-                while j.checked_add(32).is_some_and(|j32| j32 <= size)
+                while j.checked_add(32).is_some_and(|j32| j32 <= unpack)
                     && inexcept.checked_add(k).is_some_and(|ie| ie <= n)
                 {
                     bitunpacking::fast_unpack(
                         input,
                         inexcept as usize,
-                        &mut self.exception_buffers[k as usize],
+                        &mut self.scratch.exception_buffers[k as usize],
                         j as usize,
                         k as u8,
                     );
                     inexcept += k; // safe: loop guard checked inexcept + k <= n <= u32::MAX
-                    j += 32; // safe: loop guard checked j + 32 <= size
+                    j += 32; // safe: loop guard checked j + 32 <= unpack
                 }
                 // Handle the final partial group using a stack buffer (mirrors C++ buffer[PACKSIZE*2])
-                if j < size {
-                    let words_needed = (size - j) // safe: j < size
+                if j < unpack {
+                    let words_needed = (unpack - j) // safe: j < unpack
                         .saturating_mul(k)
                         .div_ceil(32);
                     let avail = n - inexcept.min(n);
@@ -399,24 +863,23 @@ impl<const N: usize> FastPFor<N> {
                     bitunpacking::fast_unpack(
                         &tail_buf,
                         tail_inpos,
-                        &mut self.exception_buffers[k as usize],
+                        &mut self.scratch.exception_buffers[k as usize],
                         j as usize,
                         k as u8,
                     );
-                    inexcept += k;
-                    j += 32;
                 }
-                let overflow = j - size;
-                inexcept -= (overflow * k) / 32;
+                // The last group only holds `size % 32` values, so skip just the
+                // words they occupy.
+                inexcept = section_end;
             }
         }
 
-        self.data_pointers.fill(0);
-        let mut tmp_output_offset = output_offset.position() as u32;
+        self.scratch.data_pointers.fill(0);
         let mut tmp_input_offset = input_offset.position() as u32;
+        let mut block = [0u32; N];
 
-        let run_end = this_size / N as u32;
-        for _ in 0..run_end {
+        for block_index in 0..blocks_end {
+            let skip = block_index < skip_end;
             let bits = input_bytes.get_val(byte_pos)?;
             if bits > 32 {
                 return Err(FastPForError::NotEnoughData);
@@ -424,23 +887,23 @@ impl<const N: usize> FastPFor<N> {
             byte_pos += 1;
             let num_exceptions = input_bytes.get_val(byte_pos)?;
             byte_pos += 1;
-            for k in (0..N as u32).step_by(32) {
-                let in_start = tmp_input_offset as usize;
-                let out_start = (tmp_output_offset + k) as usize;
-                let in_end = in_start
-                    .checked_add(usize::from(bits))
+            if skip {
+                // `bits` words per group of 32 values.
+                tmp_input_offset = tmp_input_offset
+                    .checked_add(u32::from(bits) * (N as u32 / 32))
                     .ok_or(FastPForError::NotEnoughData)?;
-                if in_end > input.len() {
-                    return Err(FastPForError::NotEnoughData);
-                }
-                let out_end = out_start
-                    .checked_add(32)
-                    .ok_or(FastPForError::OutputBufferTooSmall)?;
-                if out_end > output.len() {
-                    return Err(FastPForError::OutputBufferTooSmall);
+            } else {
+                for k in (0..N).step_by(32) {
+                    let in_start = tmp_input_offset as usize;
+                    let in_end = in_start
+                        .checked_add(usize::from(bits))
+                        .ok_or(FastPForError::NotEnoughData)?;
+                    if in_end > input.len() {
+                        return Err(FastPForError::NotEnoughData);
+                    }
+                    bitunpacking::fast_unpack(input, in_start, &mut block, k, bits);
+                    tmp_input_offset += u32::from(bits);
                 }
-                bitunpacking::fast_unpack(input, in_start, output, out_start, bits);
-                tmp_input_offset += u32::from(bits);
             }
             if num_exceptions > 0 {
                 let maxbits = input_bytes.get_val(byte_pos)?;
@@ -452,89 +915,197 @@ impl<const N: usize> FastPFor<N> {
                     return Err(FastPForError::NotEnoughData);
                 }
                 let index = usize::from(index);
-                if index == 1 {
+                if skip {
                     for _ in 0..num_exceptions {
-                        let pos = input_bytes.get_val(byte_pos)?;
+                        let pos: u8 = input_bytes.get_val(byte_pos)?;
                         byte_pos += 1;
                         if u32::from(pos) >= N as u32 {
                             return Err(FastPForError::NotEnoughData);
                         }
-                        let out_idx = tmp_output_offset as usize + pos as usize;
-                        if out_idx >= output.len() {
-                            return Err(FastPForError::OutputBufferTooSmall);
+                    }
+                    if index > 1 {
+                        self.scratch.data_pointers[index] += usize::from(num_exceptions);
+                        if self.scratch.data_pointers[index] > exception_sizes[index].as_usize() {
+                            return Err(FastPForError::NotEnoughData);
                         }
-                        output[out_idx] |= 1 << bits;
                     }
-                } else {
+                } else if index == 1 {
                     for _ in 0..num_exceptions {
                         let pos = input_bytes.get_val(byte_pos)?;
                         byte_pos += 1;
                         if u32::from(pos) >= N as u32 {
                             return Err(FastPForError::NotEnoughData);
                         }
-                        let out_idx = tmp_output_offset as usize + pos as usize;
-                        if out_idx >= output.len() {
-                            return Err(FastPForError::OutputBufferTooSmall);
+                        block[usize::from(pos)] |= 1 << bits;
+                    }
+                } else {
+                    for _ in 0..num_exceptions {
+                        let pos = input_bytes.get_val(byte_pos)?;
+                        byte_pos += 1;
+                        if u32::from(pos) >= N as u32 {
+                            return Err(FastPForError::NotEnoughData);
                         }
-                        let ptr = self.data_pointers[index];
-                        let except_value = self.exception_buffers[index].get_val(ptr)?;
-                        output[out_idx] |= except_value << bits;
-                        self.data_pointers[index] += 1;
+                        let ptr = self.scratch.data_pointers[index];
+                        let except_value = self.scratch.exception_buffers[index].get_val(ptr)?;
+                        block[usize::from(pos)] |= except_value << bits;
+                        self.scratch.data_pointers[index] += 1;
                     }
                 }
             }
-            tmp_output_offset += N as u32;
+            if !skip && emit(&block)?.is_break() {
+                return Ok(ControlFlow::Break(()));
+            }
         }
-        output_offset.set_position(u64::from(tmp_output_offset));
-        input_offset.set_position(u64::from(inexcept));
-        Ok(())
+        // Once every block is walked, the packed values and metadata must end
+        // exactly where the page header says, and the page where its checksum
+        // prefix says.
+        if blocks_end == run_end
+            && (tmp_input_offset != init_pos + where_meta || byte_pos > metadata_end)
+            || page_end.is_some_and(|end| end != inexcept)
+        {
+            return Err(FastPForError::NotEnoughData);
+        }
+        input_offset.set_position(u64::from(page_end.unwrap_or(inexcept)));
+        Ok(ControlFlow::Continue(()))
     }
 }
 
-impl<const N: usize> BlockCodec for FastPFor<N>
+/// Byte view of `u32` words in little-endian order: byte `i` is byte `i % 4`
+/// (least significant first) of word `i / 4`, on every host.
+#[derive(Clone, Copy)]
+struct LeBytes<'a>(&'a [u32]);
+
+impl GetWithErr<u8> for LeBytes<'_> {
+    #[inline]
+    fn get_val(&self, pos: impl AsUsize) -> FastPForResult<u8> {
+        let pos = pos.as_usize();
+        let word = self.0.get_val(pos / 4)?;
+        Ok(word.to_le_bytes()[pos % 4])
+    }
+}
+
+impl<const N: usize, T: Element> BlockCodec<T> for FastPFor<N, T>
 where
     [u32; N]: sealed::BlockSize,
 {
-    type Block = [u32; N];
+    type Block = [T; N];
 
     fn encode_blocks(&mut self, blocks: &[Self::Block], out: &mut Vec<u32>) -> FastPForResult<()> {
-        let n_values = (blocks.len() * N) as u32;
-        if blocks.is_empty() {
-            out.push(n_values);
-            return Ok(());
-        }
-        let flat: &[u32] = cast_slice(blocks);
-
-        let capacity = flat.len() * 2 + 1024;
-        let start = out.len();
-        // Reserve slot for the length header, then space for compressed data.
-        out.resize(start + 1 + capacity, 0);
+        // Check the header first, so that oversized input fails before allocating.
+        let n_values = self.length_header(blocks.len() * N)?;
+        let bound = self.max_compressed_len(n_values.as_usize());
+        encode_with_bound(out, bound, |out| self.encode_blocks_into(blocks, out))
+    }
 
-        let mut in_off = Cursor::new(0u32);
-        let mut out_off = Cursor::new(0u32);
+    /// Per page, the packed values and exceptions never take more bits than the
+    /// input (the bit-width choice only accepts exceptions when they are cheaper),
+    /// plus at most `N + 3` metadata bytes per block, three fixed words, and one
+    /// count word and one rounding word per exception bit width. Packing the last
+    /// exception group may briefly write up to 32 words past the final position.
+    /// Checksums add two words per page.
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        let words = n_values.div_ceil(N) * N * T::LANES;
+        let pages = words.div_ceil(self.values_per_page() * T::LANES);
+        let metadata = (words / N * (N + 3)).div_ceil(4) + pages;
+        let checksums = if self.config.checksums { 2 * pages } else { 0 };
+        1 + words + metadata + pages * (3 + 2 * 31) + checksums + 32 * pages.min(1)
+    }
 
-        // Write length header then compress.
-        out[start] = n_values;
-        self.compress_blocks(
-            flat,
-            n_values,
-            &mut in_off,
-            &mut out[start + 1..],
-            &mut out_off,
-        );
+    fn page_len(&self) -> Option<usize> {
+        Some(self.values_per_page())
+    }
 
-        let written = 1 + out_off.position() as usize;
-        out.truncate(start + written);
-        Ok(())
+    fn encode_blocks_into(
+        &mut self,
+        blocks: &[Self::Block],
+        out: &mut [u32],
+    ) -> FastPForResult<usize> {
+        let n_values = self.length_header(blocks.len() * N)?;
+        if out.len() < self.max_compressed_len(n_values.as_usize()) {
+            return Err(FastPForError::OutputBufferTooSmall);
+        }
+        out[0] = n_values;
+        let flat: &[T] = cast_slice(blocks);
+        let mut out_off = Cursor::new(0u32);
+        if let Some(words) = T::as_words(flat) {
+            self.compress_blocks(
+                words,
+                n_values,
+                &mut Cursor::new(0u32),
+                &mut out[1..],
+                &mut out_off,
+                None,
+            );
+        } else {
+            let mut page_buffer = core::mem::take(&mut self.scratch.page_buffer);
+            self.compress_lanes(flat, &mut page_buffer, &mut out[1..], &mut out_off);
+            self.scratch.page_buffer = page_buffer;
+        }
+        Ok(1 + out_off.position() as usize)
     }
 
     fn decode_blocks(
         &mut self,
         input: &[u32],
         expected_len: Option<u32>,
-        out: &mut Vec<u32>,
+        out: &mut Vec<T>,
+    ) -> FastPForResult<usize> {
+        let n_values = Self::read_length_header(input, expected_len)?;
+        out.reserve(n_values);
+        let consumed = self.decode_values(&input[1..], n_values, 0, &mut |values| {
+            out.extend_from_slice(values);
+            Ok(ControlFlow::Continue(()))
+        })?;
+        // +1 for the header word (n_values) that precedes the blocks.
+        Ok(1 + consumed)
+    }
+
+    fn decode_blocks_into(
+        &mut self,
+        input: &[u32],
+        expected_len: Option<u32>,
+        out: &mut [T],
+    ) -> FastPForResult<(usize, usize)> {
+        let n_values = Self::read_length_header(input, expected_len)?;
+        let mut blocks = out
+            .get_mut(..n_values)
+            .ok_or(FastPForError::OutputBufferTooSmall)?
+            .chunks_exact_mut(N);
+        let consumed = self.decode_values(&input[1..], n_values, 0, &mut |values| {
+            // `decode_values` emits exactly `n_values / N` blocks.
+            if let Some(dst) = blocks.next() {
+                dst.copy_from_slice(values);
+            }
+            Ok(ControlFlow::Continue(()))
+        })?;
+        Ok((1 + consumed, n_values))
+    }
+}
+
+impl<const N: usize, T: Element> FastPFor<N, T>
+where
+    [u32; N]: sealed::BlockSize,
+{
+    /// Converts the number of values to encode into the `u32` length header.
+    ///
+    /// Output positions are `u32` too, so inputs whose worst-case encoded size
+    /// does not fit in `u32` words are rejected as well. Larger inputs need
+    /// [`ChunkedCodec`](crate::ChunkedCodec).
+    fn length_header(&self, n_values: usize) -> FastPForResult<u32> {
+        let header =
+            u32::try_from(n_values).map_err(|_| FastPForError::InvalidInputLength(n_values))?;
+        if self.max_compressed_len(n_values) > u32::MAX.as_usize() {
+            return Err(FastPForError::InvalidInputLength(n_values));
+        }
+        Ok(header)
+    }
+
+    /// Reads and validates the length header that starts every `decode_blocks` input.
+    pub(crate) fn read_length_header(
+        input: &[u32],
+        expected_len: Option<u32>,
     ) -> FastPForResult<usize> {
-        let Some((&block_n_values, rest)) = input.split_first() else {
+        let Some(&block_n_values) = input.first() else {
             return Err(FastPForError::NotEnoughData);
         };
         if block_n_values % N as u32 != 0 {
@@ -548,35 +1119,377 @@ where
                 });
             }
         } else {
-            let max = Self::max_decompressed_len(input.len());
+            let max = <Self as BlockCodec<T>>::max_decompressed_len(input.len());
             if block_n_values.as_usize() > max {
                 return Err(FastPForError::NotEnoughData);
             }
         }
-        let n_blocks = block_n_values as usize / N;
-        if n_blocks == 0 {
-            return Ok(1);
-        }
-        let start = out.len();
-        out.resize(start + n_blocks * N, 0);
+        Ok(block_n_values.as_usize())
+    }
 
+    /// Decodes `n_values` values (a multiple of `N`) from the blocks that follow
+    /// the length header, passing each block of `N` values to `emit`. Returns the
+    /// number of words consumed.
+    ///
+    /// `first_page` is the index in the stream of the page that `input` starts with.
+    /// Decoding stops as soon as `emit` breaks, and the returned count is then
+    /// not meaningful.
+    fn decode_values(
+        &mut self,
+        input: &[u32],
+        n_values: usize,
+        first_page: usize,
+        emit: &mut impl FnMut(&[T]) -> FastPForResult<ControlFlow<()>>,
+    ) -> FastPForResult<usize> {
         let mut in_off = Cursor::new(0u32);
-        let mut out_off = Cursor::new(0u32);
+        if T::LANES == 1 {
+            let mut values = [T::default(); N];
+            let n_values = n_values as u32;
+            // A break is seen by the caller through `emit`.
+            let _ = self.decode_headless_blocks(
+                input,
+                n_values,
+                &mut in_off,
+                first_page,
+                &mut |block| {
+                    if let Some(values) = T::from_words(block) {
+                        return emit(values);
+                    }
+                    for (dst, &word) in values.iter_mut().zip(block) {
+                        let value = u64::from(word);
+                        *dst = T::from_u64(value).ok_or(FastPForError::ValueOutOfRange(value))?;
+                    }
+                    emit(&values)
+                },
+            )?;
+        } else {
+            let mut page_buffer = core::mem::take(&mut self.scratch.page_buffer);
+            let result = self.decode_lanes(
+                input,
+                &mut in_off,
+                &mut page_buffer,
+                n_values,
+                first_page,
+                emit,
+            );
+            self.scratch.page_buffer = page_buffer;
+            let _ = result?;
+        }
+        Ok(in_off.position() as usize)
+    }
+
+    /// Decodes a stream written by [`encode_blocks`](BlockCodec::encode_blocks),
+    /// passing each block of `N` values to `f` instead of collecting them.
+    ///
+    /// Blocks are decoded into a single scratch buffer, so aggregations such as
+    /// sums or filters need no output allocation. Returning
+    /// [`ControlFlow::Break`] from `f` stops decoding at once, without unpacking
+    /// the rest of the page or calling `f` again; the break value is returned.
+    ///
+    /// ```
+    /// use std::ops::ControlFlow;
+    ///
+    /// use fastpfor::{BlockCodec, FastPForBlock128, slice_to_blocks};
+    ///
+    /// let data: Vec<u32> = (0..512).collect();
+    /// let mut codec = FastPForBlock128::default();
+    /// let (blocks, _) = slice_to_blocks::<FastPForBlock128>(&data);
+    /// let mut encoded = Vec::new();
+    /// codec.encode_blocks(blocks, &mut encoded).unwrap();
+    ///
+    /// // Find the first block whose values exceed 300.
+    /// let mut index = 0;
+    /// let found = codec
+    ///     .decode_with(&encoded, |block| {
+    ///         if block.iter().any(|&v| v > 300) {
+    ///             return ControlFlow::Break(index);
+    ///         }
+    ///         index += 1;
+    ///         ControlFlow::Continue(())
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(found, ControlFlow::Break(2));
+    /// ```
+    pub fn decode_with<B>(
+        &mut self,
+        input: &[u32],
+        mut f: impl FnMut(&[T]) -> ControlFlow<B>,
+    ) -> FastPForResult<ControlFlow<B>> {
+        Ok(self.visit_blocks(input, &mut f)?.0)
+    }
+
+    /// Like [`decode_with`](Self::decode_with), also returning the number of
+    /// words consumed so that a tail codec can continue after the blocks.
+    pub(crate) fn visit_blocks<B>(
+        &mut self,
+        input: &[u32],
+        f: &mut impl FnMut(&[T]) -> ControlFlow<B>,
+    ) -> FastPForResult<(ControlFlow<B>, usize)> {
+        let mut remaining = Self::read_length_header(input, None)?;
+        // +1 for the header word (n_values) that precedes the blocks.
+        let mut consumed = 1;
+        let mut flow = ControlFlow::Continue(());
+        let mut page = 0;
+        while remaining != 0 && flow.is_continue() {
+            let n_values = remaining.min(self.values_per_page());
+            consumed += self.decode_values(&input[consumed..], n_values, page, &mut |values| {
+                flow = f(values);
+                Ok(match flow {
+                    ControlFlow::Continue(()) => ControlFlow::Continue(()),
+                    ControlFlow::Break(_) => ControlFlow::Break(()),
+                })
+            })?;
+            remaining -= n_values;
+            page += 1;
+        }
+        Ok((flow, consumed))
+    }
 
-        self.decode_headless_blocks(
-            rest,
-            block_n_values,
-            &mut in_off,
-            &mut out[start..],
-            &mut out_off,
+    /// Decodes page number `page` from the `remaining` values left after the
+    /// length header, appending them to `out` and subtracting them from
+    /// `remaining`. Returns the number of words consumed.
+    pub(crate) fn decode_next_page(
+        &mut self,
+        input: &[u32],
+        page: usize,
+        remaining: &mut usize,
+        out: &mut Vec<T>,
+    ) -> FastPForResult<usize> {
+        let n_values = (*remaining).min(self.values_per_page());
+        let consumed = self.decode_values(input, n_values, page, &mut |values| {
+            out.extend_from_slice(values);
+            Ok(ControlFlow::Continue(()))
+        })?;
+        *remaining -= n_values;
+        Ok(consumed)
+    }
+}
+
+impl<const N: usize> FastPFor<N>
+where
+    [u32; N]: sealed::BlockSize,
+{
+    /// Checks that `input` starts with a well-formed stream written by
+    /// [`encode_blocks`](BlockCodec::encode_blocks), without decoding it.
+    ///
+    /// Walks every page header, metadata byte and exception bitmap as
+    /// [`decode_blocks`](BlockCodec::decode_blocks) does, verifying checksums if
+    /// enabled, but skips unpacking. Words after the last page are not part of
+    /// the stream and are not counted in [`StreamInfo::consumed_words`].
+    ///
+    /// ```
+    /// use fastpfor::{BlockCodec, FastPForBlock256, slice_to_blocks};
+    ///
+    /// let data: Vec<u32> = (0..1024).collect();
+    /// let mut codec = FastPForBlock256::default();
+    /// let mut encoded = Vec::new();
+    /// codec.encode_blocks(slice_to_blocks::<FastPForBlock256>(&data).0, &mut encoded).unwrap();
+    ///
+    /// let info = codec.validate(&encoded).unwrap();
+    /// assert_eq!(info.value_count(), 1024);
+    /// assert_eq!(info.consumed_words(), encoded.len());
+    ///
+    /// encoded.truncate(encoded.len() - 1);
+    /// assert!(codec.validate(&encoded).is_err());
+    /// ```
+    pub fn validate(&mut self, input: &[u32]) -> FastPForResult<StreamInfo> {
+        let block_values = Self::read_length_header(input, None)?;
+        let (_, pages_end) = self.skip_pages(input, block_values)?;
+        Ok(StreamInfo::new(
+            block_values,
+            block_values.div_ceil(self.values_per_page()),
+            pages_end,
+        ))
+    }
+
+    /// Concatenates streams written by [`encode_blocks`](BlockCodec::encode_blocks)
+    /// into one, without decoding them.
+    ///
+    /// Pages are independent, so their words are copied as they are and only
+    /// the length header is rewritten. A decoder expects every page but the
+    /// last to be full, so every input but the last non-empty one must hold a
+    /// whole number of pages (65536 values with the default page size);
+    /// otherwise [`FastPForError::PartialPageConcat`] names the input. Page
+    /// headers are walked, and checksums verified, to find where each input
+    /// ends, and trailing words are rejected.
+    ///
+    /// To merge [`CompositeCodec`](crate::CompositeCodec) streams, whose tails
+    /// cannot be spliced, use [`SegmentedCodec`](crate::SegmentedCodec).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fastpfor::{BlockCodec, FastPForBlock256, FastPForConfig, slice_to_blocks};
+    ///
+    /// let config = FastPForConfig::new(512).unwrap();
+    /// let mut codec = FastPForBlock256::from_config(config);
+    /// let data: Vec<u32> = (0..2560).collect();
+    /// let (first, second) = data.split_at(1024);
+    ///
+    /// let mut a = Vec::new();
+    /// codec.encode_blocks(slice_to_blocks::<FastPForBlock256>(first).0, &mut a).unwrap();
+    /// let mut b = Vec::new();
+    /// codec.encode_blocks(slice_to_blocks::<FastPForBlock256>(second).0, &mut b).unwrap();
+    ///
+    /// let merged = codec.concat_encoded(&[&a, &b]).unwrap();
+    /// let mut decoded = Vec::new();
+    /// codec.decode_blocks(&merged, None, &mut decoded).unwrap();
+    /// assert_eq!(decoded, data);
+    /// ```
+    pub fn concat_encoded(&mut self, inputs: &[&[u32]]) -> FastPForResult<Vec<u32>> {
+        let page_values = self.values_per_page();
+        let last_non_empty = inputs
+            .iter()
+            .rposition(|input| input.first().is_some_and(|&n| n != 0));
+        let mut out = Vec::from([0]);
+        let mut total: usize = 0;
+        for (i, input) in inputs.iter().enumerate() {
+            let block_values = Self::read_length_header(input, None)?;
+            if block_values % page_values != 0 && Some(i) != last_non_empty {
+                return Err(FastPForError::PartialPageConcat { input: i });
+            }
+            let (_, pages_end) = self.skip_pages(input, block_values)?;
+            if pages_end != input.len() {
+                return Err(FastPForError::InvalidInputLength(input.len()));
+            }
+            out.extend_from_slice(&input[1..pages_end]);
+            total += block_values;
+        }
+        out[0] = self.length_header(total)?;
+        Ok(out)
+    }
+
+    /// Walks the pages of a stream holding `block_values` values without
+    /// unpacking them, checking their metadata and verifying checksums if enabled.
+    ///
+    /// Returns the position of the last page (of the end of the header if there
+    /// are no pages) and the position just past the pages.
+    pub(crate) fn skip_pages(
+        &mut self,
+        input: &[u32],
+        block_values: usize,
+    ) -> FastPForResult<(usize, usize)> {
+        let page_values = self.values_per_page();
+        let mut in_off = Cursor::new(1u32);
+        let mut last_page_start = 1;
+        let mut remaining = block_values;
+        let mut page = 0;
+        while remaining > 0 {
+            last_page_start = in_off.position() as usize;
+            let this_size = page_values.min(remaining);
+            let _ = self.decode_page_blocks(
+                input,
+                &mut in_off,
+                this_size as u32,
+                page,
+                0..0,
+                &mut |_| Ok(ControlFlow::Continue(())),
+            )?;
+            remaining -= this_size;
+            page += 1;
+        }
+        Ok((last_page_start, in_off.position() as usize))
+    }
+
+    /// Appends to `out` the page `page`, number `page_index` in its stream,
+    /// which holds `page_values` values, with `values` (whole blocks) added at
+    /// its end.
+    ///
+    /// The page's packed blocks and metadata bytes are copied as they are; only
+    /// its exception sections are unpacked, to be packed again together with
+    /// those of the new blocks. The caller keeps the result within a page.
+    pub(crate) fn extend_page(
+        &mut self,
+        page: &[u32],
+        page_index: usize,
+        page_values: usize,
+        values: &[u32],
+        out: &mut Vec<u32>,
+    ) -> FastPForResult<()> {
+        let n_values = self.length_header(values.len())?;
+        // Walking the page up to its last block leaves every one of its
+        // exceptions in the scratch buffers, counted in `data_pointers`.
+        let blocks = u32::try_from(page_values / N).map_err(|_| FastPForError::NotEnoughData)?;
+        let _ = self.decode_page_blocks(
+            page,
+            &mut Cursor::new(0),
+            page_values as u32,
+            page_index,
+            blocks.saturating_sub(1)..blocks,
+            &mut |_| Ok(ControlFlow::Continue(())),
         )?;
 
-        let written = out_off.position() as usize;
-        if written != n_blocks * N {
-            out.truncate(start + written);
+        let header = if self.config.checksums { 2 } else { 0 };
+        let meta = header + page.get_val(header)?.as_usize();
+        let packed = page
+            .get(header + 1..meta)
+            .ok_or(FastPForError::NotEnoughData)?;
+        let byte_size = page.get_val(meta)?.as_usize();
+        let bytes = LeBytes(page);
+        self.scratch.bytes_container.clear();
+        for pos in (meta + 1) * 4..(meta + 1) * 4 + byte_size {
+            self.scratch.bytes_container.push(bytes.get_val(pos)?);
         }
-        // +1 for the header word (block_n_values) that precedes `rest`.
-        Ok(1 + in_off.position() as usize)
+
+        // The repacked exceptions take no more words than the page's own
+        // sections plus those of the new blocks, and may overshoot by a group.
+        let bound = page.len() + self.max_compressed_len(values.len()) + 32;
+        encode_with_bound(out, bound, |out| {
+            let mut out_off = Cursor::new(header as u32);
+            out_off.increment();
+            out[header + 1..meta].copy_from_slice(packed);
+            out_off.add(packed.len() as u32);
+            self.pack_blocks(values, n_values, &mut Cursor::new(0), out, &mut out_off);
+            self.finish_page(0, header, out, &mut out_off);
+            Ok(out_off.position() as usize)
+        })
+    }
+
+    /// Like [`encode_blocks`](BlockCodec::encode_blocks), also appending to
+    /// `page_starts` the position in `out` of each page, followed by the
+    /// position just past the last page.
+    pub(crate) fn encode_blocks_indexed(
+        &mut self,
+        blocks: &[[u32; N]],
+        out: &mut Vec<u32>,
+        page_starts: &mut Vec<u32>,
+    ) -> FastPForResult<()> {
+        let values = blocks.as_flattened();
+        out.push(self.length_header(values.len())?);
+        self.encode_pages(values, out, page_starts)?;
+        page_starts.push(out.len() as u32);
+        Ok(())
+    }
+
+    /// Appends `values` (whole blocks) to `out` as pages, without a length
+    /// header, and appends the position in `out` of each page to `page_starts`.
+    pub(crate) fn encode_pages(
+        &mut self,
+        values: &[u32],
+        out: &mut Vec<u32>,
+        page_starts: &mut Vec<u32>,
+    ) -> FastPForResult<()> {
+        let n_values = self.length_header(values.len())?;
+        // `max_compressed_len` includes the length header.
+        let bound = self.max_compressed_len(values.len()) - 1;
+        let base = out.len() as u32;
+        let first_page = page_starts.len();
+        encode_with_bound(out, bound, |out| {
+            let mut out_off = Cursor::new(0u32);
+            self.compress_blocks(
+                values,
+                n_values,
+                &mut Cursor::new(0u32),
+                out,
+                &mut out_off,
+                Some(page_starts),
+            );
+            Ok(out_off.position() as usize)
+        })?;
+        for start in &mut page_starts[first_page..] {
+            *start += base;
+        }
+        Ok(())
     }
 }
 
@@ -584,6 +1497,7 @@ where
 mod tests {
     use super::*;
     use crate::test_utils::{block_compress, block_decompress, block_roundtrip};
+    use crate::{AnyLenCodec as _, slice_to_blocks, slice_to_blocks_of};
 
     #[test]
     fn fastpfor_test() {
@@ -660,15 +1574,12 @@ mod tests {
 
     #[test]
     fn headless_uncompress_zero_inlength_128_ok() {
-        FastPForBlock128::default()
-            .decode_headless_blocks(
-                &[],
-                0,
-                &mut Cursor::new(0u32),
-                &mut [],
-                &mut Cursor::new(0u32),
-            )
+        let flow = FastPForBlock128::default()
+            .decode_headless_blocks(&[], 0, &mut Cursor::new(0u32), 0, &mut |_| {
+                panic!("no block to emit")
+            })
             .expect("zero-length decompress must succeed");
+        assert!(flow.is_continue());
     }
 
     #[test]
@@ -689,8 +1600,8 @@ mod tests {
                     &padded,
                     out_length,
                     &mut Cursor::new(1u32),
-                    &mut vec![0u32; 320],
-                    &mut Cursor::new(0u32),
+                    0,
+                    &mut |_| Ok(ControlFlow::Continue(())),
                 )
                 .is_err()
         );
@@ -703,6 +1614,99 @@ mod tests {
         block_roundtrip::<FastPForBlock256>(&data);
     }
 
+    #[test]
+    fn narrow_u16_block_roundtrip() {
+        let data: Vec<u16> = (0..512u32).map(|i| (i * 131 % 65536) as u16).collect();
+        let mut codec = FastPFor::<256, u16>::default();
+        let (blocks, _) = slice_to_blocks_of::<FastPFor<256, u16>, _>(&data);
+        let mut encoded = Vec::new();
+        codec.encode_blocks(blocks, &mut encoded).unwrap();
+        let mut decoded = Vec::new();
+        codec
+            .decode_blocks(&encoded, Some(512), &mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn narrow_u8_matches_u32_wire_format_across_pages() {
+        // A 256-value page size forces the widening loop over several pages.
+        let data: Vec<u8> = (0..1024u32).map(|i| (i % 7 * 37) as u8).collect();
+        let widened: Vec<u32> = data.iter().map(|&v| u32::from(v)).collect();
+
+        let mut narrow = FastPFor::<128, u8>::new(256).unwrap();
+        let (blocks, _) = slice_to_blocks_of::<FastPFor<128, u8>, _>(&data);
+        let mut encoded = Vec::new();
+        narrow.encode_blocks(blocks, &mut encoded).unwrap();
+
+        let mut wide = FastPForBlock128::new(256).unwrap();
+        let (blocks, _) = slice_to_blocks::<FastPForBlock128>(&widened);
+        let mut expected = Vec::new();
+        wide.encode_blocks(blocks, &mut expected).unwrap();
+        assert_eq!(encoded, expected);
+
+        let mut decoded = Vec::new();
+        narrow.decode_blocks(&encoded, None, &mut decoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn narrow_decode_rejects_out_of_range_values() {
+        let mut data = vec![1u32; 256];
+        data[7] = 70_000;
+        let encoded = block_compress::<FastPForBlock256>(&data).unwrap();
+        let mut decoded: Vec<u16> = Vec::new();
+        let err = FastPFor::<256, u16>::default()
+            .decode_blocks(&encoded, None, &mut decoded)
+            .unwrap_err();
+        assert!(matches!(err, FastPForError::ValueOutOfRange(70_000)));
+    }
+
+    #[test]
+    fn wide_u64_roundtrip_with_single_block_pages() {
+        // With `page_size == N` every page holds one block, i.e. 2N `u32` words.
+        let data: Vec<u64> = (0..512u64)
+            .map(|i| if i % 3 == 0 { u64::MAX - i } else { i })
+            .collect();
+        let mut codec = FastPFor::<128, u64>::new(128).unwrap();
+        let (blocks, _) = slice_to_blocks_of::<FastPFor<128, u64>, _>(&data);
+        let mut encoded = Vec::new();
+        codec.encode_blocks(blocks, &mut encoded).unwrap();
+        let mut decoded = Vec::new();
+        codec
+            .decode_blocks(&encoded, Some(512), &mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn wide_u64_small_values_add_little_overhead() {
+        let data: Vec<u64> = (0..1024u64).map(|i| i % 100).collect();
+        let widened: Vec<u32> = data.iter().map(|&v| v as u32).collect();
+        let (blocks, _) = slice_to_blocks_of::<FastPFor<256, u64>, _>(&data);
+        let mut encoded = Vec::new();
+        FastPFor::<256, u64>::default()
+            .encode_blocks(blocks, &mut encoded)
+            .unwrap();
+        let narrow = block_compress::<FastPForBlock256>(&widened).unwrap();
+        // The all-zero high lanes only cost their per-block metadata.
+        assert!(encoded.len() < narrow.len() + 8, "{encoded:?}");
+    }
+
+    #[test]
+    fn signed_i32_matches_u32_wire_format() {
+        let data: Vec<i32> = (0..256i32).map(|i| i - 128).collect();
+        let bits: Vec<u32> = data.iter().map(|&v| v as u32).collect();
+        let (blocks, _) = slice_to_blocks_of::<FastPFor<256, i32>, _>(&data);
+        let mut encoded = Vec::new();
+        let mut codec = FastPFor::<256, i32>::default();
+        codec.encode_blocks(blocks, &mut encoded).unwrap();
+        assert_eq!(encoded, block_compress::<FastPForBlock256>(&bits).unwrap());
+        let mut decoded = Vec::new();
+        codec.decode_blocks(&encoded, None, &mut decoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
     /// `decode_blocks` with `expected_len: None` and header=0 returns `Ok` with empty output.
     #[test]
     fn decode_blocks_header_only_input() {
@@ -711,4 +1715,418 @@ mod tests {
         let out = block_decompress::<FastPForBlock256>(&input, None).unwrap();
         assert!(out.is_empty());
     }
+
+    #[test]
+    fn shared_scratch_matches_owned_codecs() {
+        fn assert_copy_sync<C: Copy + Send + Sync>(_: C) {}
+
+        let data: Vec<u32> = (0..1024u32)
+            .map(|i| if i % 9 == 0 { 1 << 30 } else { i % 7 })
+            .collect();
+        let wide: Vec<u64> = data.iter().map(|&v| u64::from(v) << 20).collect();
+        let mut scratch = FastPForScratch::default();
+
+        // One scratch serves configurations with different block sizes, element
+        // types and page sizes, in any order.
+        let config128 = FastPForConfig::<128>::new(256).unwrap();
+        let config256 = FastPForConfig::<256>::default();
+        let config_u64 = FastPForConfig::<128, u64>::new(128).unwrap();
+        assert_copy_sync(config128);
+        for _ in 0..2 {
+            let (blocks, _) = slice_to_blocks::<FastPForBlock128>(&data);
+            let mut encoded = Vec::new();
+            config128
+                .encode_blocks(&mut scratch, blocks, &mut encoded)
+                .unwrap();
+            let mut owned = FastPForBlock128::new(256).unwrap();
+            let mut expected = Vec::new();
+            owned.encode_blocks(blocks, &mut expected).unwrap();
+            assert_eq!(encoded, expected);
+
+            let (blocks, _) = slice_to_blocks_of::<FastPFor<128, u64>, _>(&wide);
+            let mut encoded_u64 = Vec::new();
+            config_u64
+                .encode_blocks(&mut scratch, blocks, &mut encoded_u64)
+                .unwrap();
+
+            let (blocks, _) = slice_to_blocks::<FastPForBlock256>(&data);
+            let mut encoded256 = Vec::new();
+            config256
+                .encode_blocks(&mut scratch, blocks, &mut encoded256)
+                .unwrap();
+            assert_eq!(
+                encoded256,
+                block_compress::<FastPForBlock256>(&data).unwrap()
+            );
+
+            let mut decoded = Vec::new();
+            config128
+                .decode_blocks(&mut scratch, &encoded, None, &mut decoded)
+                .unwrap();
+            assert_eq!(decoded, data);
+            let mut decoded = Vec::new();
+            config_u64
+                .decode_blocks(&mut scratch, &encoded_u64, None, &mut decoded)
+                .unwrap();
+            assert_eq!(decoded, wide);
+        }
+
+        let sum = config256.with_scratch(&mut scratch, |codec| {
+            let encoded = block_compress::<FastPForBlock256>(&data).unwrap();
+            let mut sum = 0u64;
+            let flow = codec
+                .decode_with(&encoded, |block| {
+                    sum += block.iter().map(|&v| u64::from(v)).sum::<u64>();
+                    ControlFlow::<()>::Continue(())
+                })
+                .unwrap();
+            assert!(flow.is_continue());
+            sum
+        });
+        assert_eq!(sum, data.iter().map(|&v| u64::from(v)).sum::<u64>());
+    }
+
+    #[test]
+    fn decode_blocks_into_slice() {
+        let data: Vec<u32> = (0..512u32).map(|i| i % 5 * 1000).collect();
+        let encoded = block_compress::<FastPForBlock256>(&data).unwrap();
+        let mut out = vec![7u32; 600];
+        let (consumed, written) = FastPForBlock256::default()
+            .decode_blocks_into(&encoded, None, &mut out)
+            .unwrap();
+        assert_eq!((consumed, written), (encoded.len(), 512));
+        assert_eq!(out[..512], data);
+        assert_eq!(out[512], 7, "values past `written` are untouched");
+
+        let err = FastPForBlock256::default()
+            .decode_blocks_into(&encoded, None, &mut out[..511])
+            .unwrap_err();
+        assert!(matches!(err, FastPForError::OutputBufferTooSmall));
+    }
+
+    #[test]
+    fn max_compressed_len_bounds_adversarial_pages() {
+        // Full-width values, sparse exceptions and single-block pages stress every term.
+        let patterns: [fn(u32) -> u32; 3] = [
+            |i| i.wrapping_mul(2_654_435_761),
+            |i| if i % 37 == 0 { u32::MAX - i } else { i % 3 },
+            |i| 1 << (i % 32),
+        ];
+        for pattern in patterns {
+            let data: Vec<u32> = (0..2048).map(pattern).collect();
+            for page_size in [128, 256, 65536] {
+                let mut codec = FastPForBlock128::new(page_size).unwrap();
+                let (blocks, _) = slice_to_blocks::<FastPForBlock128>(&data);
+                let bound = BlockCodec::max_compressed_len(&codec, data.len());
+                let mut out = vec![0; bound];
+                let written = codec.encode_blocks_into(blocks, &mut out).unwrap();
+                assert!(written <= bound);
+                let mut decoded = Vec::new();
+                codec
+                    .decode_blocks(&out[..written], None, &mut decoded)
+                    .unwrap();
+                assert_eq!(decoded, data);
+            }
+        }
+    }
+
+    #[test]
+    fn length_header_keeps_output_positions_in_u32() {
+        let limit = u32::MAX as usize;
+        let codec = FastPForBlock256::default();
+        let largest = limit / 256 * 256;
+        assert!(BlockCodec::max_compressed_len(&codec, largest) > limit);
+        assert!(matches!(
+            codec.length_header(largest),
+            Err(FastPForError::InvalidInputLength(n)) if n == largest
+        ));
+        assert_eq!(codec.length_header(1 << 31).unwrap(), 1 << 31);
+
+        // Two lanes per value halve the largest input.
+        let wide = FastPFor::<256, u64>::default();
+        assert!(matches!(
+            wide.length_header(1 << 31),
+            Err(FastPForError::InvalidInputLength(_))
+        ));
+        assert_eq!(wide.length_header(1 << 30).unwrap(), 1 << 30);
+    }
+
+    #[test]
+    fn encode_blocks_into_rejects_short_slice() {
+        let data = vec![3u32; 256];
+        let (blocks, _) = slice_to_blocks::<FastPForBlock256>(&data);
+        let mut codec = FastPForBlock256::default();
+        let bound = BlockCodec::max_compressed_len(&codec, 256);
+        let mut out = vec![0; bound - 1];
+        let err = codec.encode_blocks_into(blocks, &mut out).unwrap_err();
+        assert!(matches!(err, FastPForError::OutputBufferTooSmall));
+        assert!(out.iter().all(|&w| w == 0), "nothing is written on error");
+    }
+
+    /// Encodes `data` with checksums and pages of 256 words, returning the stream
+    /// and the start of each page's checksum prefix.
+    fn checksummed<T: Element>(codec: &mut FastPFor<128, T>, data: &[T]) -> (Vec<u32>, Vec<usize>) {
+        let (blocks, _) = slice_to_blocks_of::<FastPFor<128, T>, _>(data);
+        let mut encoded = Vec::new();
+        codec.encode_blocks(blocks, &mut encoded).unwrap();
+        let mut pages = Vec::new();
+        let mut pos = 1;
+        while pos < encoded.len() {
+            pages.push(pos);
+            pos += 2 + encoded[pos] as usize;
+        }
+        assert_eq!(pos, encoded.len());
+        (encoded, pages)
+    }
+
+    #[test]
+    fn checksummed_pages_roundtrip() {
+        let config = FastPForConfig::<128, u32>::new(256)
+            .unwrap()
+            .with_checksums(true);
+        let mut codec = FastPFor::from_config(config);
+        let data: Vec<u32> = (0..2048u32)
+            .map(|i| i.wrapping_mul(2_654_435_761) >> (i % 32))
+            .collect();
+        let (encoded, pages) = checksummed(&mut codec, &data);
+        assert_eq!(pages.len(), 8);
+        assert!(encoded.len() <= BlockCodec::max_compressed_len(&codec, data.len()));
+        let mut decoded = Vec::new();
+        codec.decode_blocks(&encoded, None, &mut decoded).unwrap();
+        assert_eq!(decoded, data);
+
+        let mut plain = Vec::new();
+        let (blocks, _) = slice_to_blocks::<FastPForBlock128>(&data);
+        FastPForBlock128::new(256)
+            .unwrap()
+            .encode_blocks(blocks, &mut plain)
+            .unwrap();
+        assert_eq!(encoded.len(), plain.len() + 2 * pages.len());
+    }
+
+    #[test]
+    fn checksum_mismatch_reports_page_index() {
+        let config = FastPForConfig::<128, u32>::new(256)
+            .unwrap()
+            .with_checksums(true);
+        let mut codec = FastPFor::from_config(config);
+        let data: Vec<u32> = (0..2048).collect();
+        let (mut encoded, pages) = checksummed(&mut codec, &data);
+        encoded[pages[3] + 5] ^= 1 << 13;
+
+        let err = codec.decode_blocks(&encoded, None, &mut Vec::new());
+        assert!(matches!(
+            err,
+            Err(FastPForError::ChecksumMismatch { page: 3 })
+        ));
+
+        let mut blocks_seen = 0;
+        let err = codec.decode_with(&encoded, |_| {
+            blocks_seen += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        assert!(matches!(
+            err,
+            Err(FastPForError::ChecksumMismatch { page: 3 })
+        ));
+        assert_eq!(
+            blocks_seen,
+            3 * 2,
+            "pages before the corrupted one are decoded"
+        );
+
+        let mut composite = crate::CompositeCodec::new(codec, crate::VariableByte::new());
+        let err = composite
+            .decode_iter(&encoded)
+            .collect::<FastPForResult<Vec<u32>>>();
+        assert!(matches!(
+            err,
+            Err(FastPForError::ChecksumMismatch { page: 3 })
+        ));
+    }
+
+    #[test]
+    fn checksum_mismatch_in_wide_lanes() {
+        let config = FastPForConfig::<128, u64>::new(256)
+            .unwrap()
+            .with_checksums(true);
+        let mut codec = FastPFor::from_config(config);
+        let data: Vec<u64> = (0..1024u64).map(|i| i << 33 | i).collect();
+        let (mut encoded, pages) = checksummed(&mut codec, &data);
+        let mut decoded = Vec::new();
+        codec.decode_blocks(&encoded, None, &mut decoded).unwrap();
+        assert_eq!(decoded, data);
+
+        let last = pages.len() - 1;
+        *encoded.last_mut().unwrap() ^= 1;
+        let err = codec.decode_blocks(&encoded, None, &mut Vec::new());
+        assert!(matches!(
+            err,
+            Err(FastPForError::ChecksumMismatch { page }) if page == last
+        ));
+    }
+
+    #[test]
+    fn checksummed_page_truncated_is_not_enough_data() {
+        let config = FastPForConfig::<128, u32>::default().with_checksums(true);
+        let mut codec = FastPFor::from_config(config);
+        let data: Vec<u32> = (0..512).collect();
+        let (encoded, _) = checksummed(&mut codec, &data);
+        let err = codec.decode_blocks(&encoded[..encoded.len() - 1], None, &mut Vec::new());
+        assert!(matches!(err, Err(FastPForError::NotEnoughData)));
+    }
+
+    #[test]
+    fn metadata_words_are_little_endian_on_every_host() {
+        // Bit width 1, one exception of 21 bits at position 5.
+        let mut data = vec![1u32; 256];
+        data[5] = 1 << 20;
+        let encoded = block_compress::<FastPForBlock256>(&data).unwrap();
+        let where_meta = encoded[1] as usize;
+        assert_eq!(encoded[1 + where_meta], 4, "metadata byte count");
+        let meta = encoded[2 + where_meta];
+        assert_eq!(meta, u32::from_le_bytes([1, 1, 21, 5]));
+
+        // Simulate a big-endian host: it holds the same word values, but its
+        // native byte view of each word is reversed. Metadata must be read from
+        // the values, not the native view, to get the stream order back.
+        let wire: Vec<u8> = encoded.iter().flat_map(|w| w.to_le_bytes()).collect();
+        let be_native: Vec<u8> = encoded.iter().flat_map(|w| w.to_be_bytes()).collect();
+        assert_ne!(wire, be_native);
+        let bytes = LeBytes(&encoded);
+        for (i, &byte) in wire.iter().enumerate() {
+            assert_eq!(bytes.get_val(i).unwrap(), byte);
+        }
+        assert_eq!(
+            block_decompress::<FastPForBlock256>(&encoded, None).unwrap(),
+            data
+        );
+
+        // `FastPFor256` bytes of `data`, written on a little-endian host. Every
+        // host must read them back, and write them unchanged.
+        #[rustfmt::skip]
+        let golden: [u8; 60] = [
+            0, 1, 0, 0,                  // block values: 256
+            9, 0, 0, 0,                  // metadata offset
+            223, 255, 255, 255,          // packed 1-bit values
+            255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 255, 255,
+            4, 0, 0, 0,                  // metadata byte count
+            1, 1, 21, 5,                 // bit width, exceptions, max bits, position
+            0, 0, 8, 0,                  // exception width bitmap
+            1, 0, 0, 0,                  // 1 exception of width 20
+            0, 0, 8, 0,                  // its packed high bits
+            // no `VariableByte` tail values
+        ];
+        let mut codec = crate::FastPFor256::default();
+        let mut decoded = Vec::new();
+        codec
+            .decode_from_bytes(&golden, &mut decoded, None)
+            .unwrap();
+        assert_eq!(decoded, data);
+        let mut bytes = Vec::new();
+        codec.encode_to_bytes(&data, &mut bytes).unwrap();
+        assert_eq!(bytes, golden);
+    }
+
+    fn encode_with(codec: &mut FastPForBlock128, data: &[u32]) -> Vec<u32> {
+        let (blocks, _) = slice_to_blocks::<FastPForBlock128>(data);
+        let mut encoded = Vec::new();
+        codec.encode_blocks(blocks, &mut encoded).unwrap();
+        encoded
+    }
+
+    #[test]
+    fn concat_encoded_splices_pages() {
+        for checksums in [false, true] {
+            let config = FastPForConfig::new(256).unwrap().with_checksums(checksums);
+            let mut codec = FastPForBlock128::from_config(config);
+            let data: Vec<u32> = (0..3200u32)
+                .map(|i| i.wrapping_mul(2_654_435_761) >> (i % 32))
+                .collect();
+            // Whole pages, no pages, then a partial last page.
+            let parts = [&data[..512], &data[..0], &data[512..2048], &data[2048..]];
+            let encoded: Vec<Vec<u32>> = parts.iter().map(|p| encode_with(&mut codec, p)).collect();
+            let inputs: Vec<&[u32]> = encoded.iter().map(Vec::as_slice).collect();
+
+            let merged = codec.concat_encoded(&inputs).unwrap();
+            let words: usize = encoded.iter().map(|e| e.len() - 1).sum();
+            assert_eq!(merged.len(), 1 + words);
+            let mut decoded = Vec::new();
+            codec.decode_blocks(&merged, None, &mut decoded).unwrap();
+            assert_eq!(decoded, data);
+        }
+    }
+
+    #[test]
+    fn concat_encoded_partial_page_must_be_last() {
+        let mut codec = FastPForBlock128::new(256).unwrap();
+        let data: Vec<u32> = (0..1024).collect();
+        let partial = encode_with(&mut codec, &data[..384]);
+        let whole = encode_with(&mut codec, &data[..512]);
+        let empty = encode_with(&mut codec, &[]);
+        assert!(matches!(
+            codec.concat_encoded(&[&whole, &partial, &whole]),
+            Err(FastPForError::PartialPageConcat { input: 1 })
+        ));
+        // Trailing empty inputs do not add pages.
+        let merged = codec.concat_encoded(&[&whole, &partial, &empty]).unwrap();
+        assert_eq!(merged[0], 896);
+    }
+
+    #[test]
+    fn concat_encoded_rejects_malformed_inputs() {
+        let mut codec = FastPForBlock128::new(256).unwrap();
+        let mut encoded = encode_with(&mut codec, &(0..512).collect::<Vec<_>>());
+        encoded.push(0);
+        assert!(matches!(
+            codec.concat_encoded(&[&encoded]),
+            Err(FastPForError::InvalidInputLength(_))
+        ));
+        encoded.truncate(encoded.len() - 3);
+        assert!(codec.concat_encoded(&[&encoded]).is_err());
+        assert!(codec.concat_encoded(&[&[]]).is_err());
+    }
+
+    #[test]
+    fn validate_stops_at_last_page() {
+        let mut codec = FastPForBlock128::new(256).unwrap();
+        let data: Vec<u32> = (0..640).map(|i| i * 31 % 1000).collect();
+        let mut encoded = encode_with(&mut codec, &data);
+        let words = encoded.len();
+        encoded.extend([7, 7, 7]);
+        let info = codec.validate(&encoded).unwrap();
+        assert_eq!(info.value_count(), 640);
+        assert_eq!(info.page_count(), 3);
+        assert_eq!(info.consumed_words(), words);
+    }
+
+    #[test]
+    fn validate_checks_checksums() {
+        let config = FastPForConfig::new(256).unwrap().with_checksums(true);
+        let mut codec = FastPForBlock128::from_config(config);
+        let mut encoded = encode_with(&mut codec, &(0..1024).collect::<Vec<_>>());
+        assert_eq!(codec.validate(&encoded).unwrap().page_count(), 4);
+        let last = encoded.len() - 1;
+        encoded[last] ^= 1;
+        assert!(matches!(
+            codec.validate(&encoded),
+            Err(FastPForError::ChecksumMismatch { page: 3 })
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_exception_count_beyond_section() {
+        let mut data = vec![1u32; 128];
+        data[3] = 1 << 20;
+        data[9] = 1 << 20;
+        let mut encoded = encode_with(&mut FastPForBlock128::default(), &data);
+        let where_meta = encoded[1] as usize;
+        let bitmap_idx = 1 + where_meta + 1 + (encoded[1 + where_meta] as usize).div_ceil(4);
+        // The single exception width holds 2 values; claim only 1.
+        assert_eq!(encoded[bitmap_idx + 1], 2);
+        encoded[bitmap_idx + 1] = 1;
+        assert!(FastPForBlock128::default().validate(&encoded).is_err());
+    }
 }
@@ -1,10 +1,14 @@
-use crate::FastPForResult;
+use alloc::vec::Vec;
+
 use crate::codec::AnyLenCodec;
 use crate::helpers::AsUsize;
+use crate::{Element, FastPForError, FastPForResult};
 
 /// A no-op codec that copies data without compression.
 ///
 /// Useful as a baseline for benchmarking or when a codec interface is required.
+/// Every [`Element`] type is stored as [`Element::LANES`] `u32` words per value
+/// (low half first for 64-bit types).
 #[derive(Debug, Default)]
 pub struct JustCopy;
 
@@ -16,31 +20,83 @@ impl JustCopy {
     }
 }
 
-impl AnyLenCodec for JustCopy {
-    fn encode(&mut self, input: &[u32], out: &mut Vec<u32>) -> FastPForResult<()> {
-        out.extend_from_slice(input);
+impl<T: Element> AnyLenCodec<T> for JustCopy {
+    fn encode(&mut self, input: &[T], out: &mut Vec<u32>) -> FastPForResult<()> {
+        out.reserve(input.len() * T::LANES);
+        for &value in input {
+            out.extend((0..T::LANES).map(|lane| value.lane(lane)));
+        }
         Ok(())
     }
 
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        n_values * T::LANES
+    }
+
+    fn encode_into(&mut self, input: &[T], out: &mut [u32]) -> FastPForResult<usize> {
+        let n_words = input.len() * T::LANES;
+        let out = out
+            .get_mut(..n_words)
+            .ok_or(FastPForError::OutputBufferTooSmall)?;
+        for (&value, words) in input.iter().zip(out.chunks_exact_mut(T::LANES)) {
+            for (lane, word) in words.iter_mut().enumerate() {
+                *word = value.lane(lane);
+            }
+        }
+        Ok(n_words)
+    }
+
     fn decode(
         &mut self,
         input: &[u32],
-        out: &mut Vec<u32>,
+        out: &mut Vec<T>,
         expected_len: Option<u32>,
     ) -> FastPForResult<()> {
+        if input.len() % T::LANES != 0 {
+            return Err(FastPForError::InvalidInputLength(input.len()));
+        }
+        let n_values = input.len() / T::LANES;
         if let Some(expected) = expected_len {
-            let expected = expected.is_valid_expected(Self::max_decompressed_len(input.len()))?;
-            input.len().is_decoded_mismatch(expected)?;
+            let max = <Self as AnyLenCodec<T>>::max_decompressed_len(input.len());
+            let expected = expected.is_valid_expected(max)?;
+            n_values.is_decoded_mismatch(expected)?;
+        }
+        out.reserve(n_values);
+        for words in input.chunks_exact(T::LANES) {
+            let value = words
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (lane, &w)| acc | u64::from(w) << (32 * lane));
+            out.push(T::from_u64(value).ok_or(FastPForError::ValueOutOfRange(value))?);
         }
-        out.extend_from_slice(input);
         Ok(())
     }
+
+    fn decode_into(&mut self, input: &[u32], out: &mut [T]) -> FastPForResult<usize> {
+        if input.len() % T::LANES != 0 {
+            return Err(FastPForError::InvalidInputLength(input.len()));
+        }
+        let n_values = input.len() / T::LANES;
+        let out = out
+            .get_mut(..n_values)
+            .ok_or(FastPForError::OutputBufferTooSmall)?;
+        for (dst, words) in out.iter_mut().zip(input.chunks_exact(T::LANES)) {
+            let value = words
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (lane, &w)| acc | u64::from(w) << (32 * lane));
+            *dst = T::from_u64(value).ok_or(FastPForError::ValueOutOfRange(value))?;
+        }
+        Ok(n_values)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{decompress, roundtrip, roundtrip_expected};
+    use crate::test_utils::{
+        decompress, roundtrip, roundtrip_elements, roundtrip_expected, roundtrip_into,
+    };
 
     #[test]
     fn justcopy_roundtrip() {
@@ -56,4 +112,47 @@ mod tests {
     fn justcopy_decode_expected_len_mismatch_errors() {
         decompress::<JustCopy>(&[1u32, 2, 3], Some(2)).unwrap_err();
     }
+
+    #[test]
+    fn justcopy_narrow_roundtrip() {
+        roundtrip_elements::<u16, JustCopy>(&[1, 2, u16::MAX]);
+        roundtrip_elements::<u8, JustCopy>(&[0, u8::MAX]);
+    }
+
+    #[test]
+    fn justcopy_narrow_decode_rejects_out_of_range_values() {
+        let mut decoded: Vec<u16> = Vec::new();
+        let err = JustCopy
+            .decode(&[1, 65_536], &mut decoded, None)
+            .unwrap_err();
+        assert!(matches!(err, FastPForError::ValueOutOfRange(65_536)));
+    }
+
+    #[test]
+    fn justcopy_wide_roundtrip() {
+        roundtrip_elements::<u64, JustCopy>(&[1, u64::MAX, 1 << 32]);
+        roundtrip_elements::<i64, JustCopy>(&[-1, i64::MIN]);
+    }
+
+    #[test]
+    fn justcopy_wide_decode_rejects_odd_word_count() {
+        let mut decoded: Vec<u64> = Vec::new();
+        let err = JustCopy.decode(&[1, 2, 3], &mut decoded, None).unwrap_err();
+        assert!(matches!(err, FastPForError::InvalidInputLength(3)));
+    }
+
+    #[test]
+    fn justcopy_decode_into_slice() {
+        roundtrip_into::<u32, JustCopy>(&[1, 2, 3]);
+        roundtrip_into::<i64, JustCopy>(&[-1, i64::MAX]);
+    }
+
+    #[test]
+    fn justcopy_encode_into_exact_len() {
+        let mut out = [0u32; 4];
+        assert_eq!(JustCopy.encode_into(&[1u64, 1 << 32], &mut out).unwrap(), 4);
+        assert_eq!(out, [1, 0, 0, 1]);
+        let err = JustCopy.encode_into(&[1u32, 2], &mut out[..1]).unwrap_err();
+        assert!(matches!(err, FastPForError::OutputBufferTooSmall));
+    }
 }
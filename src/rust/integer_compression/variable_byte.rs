@@ -1,13 +1,20 @@
-use std::io::Cursor;
+use alloc::vec::Vec;
 
-use bytemuck::{cast_slice, cast_slice_mut};
+use bytemuck::cast_slice_mut;
 
-use crate::codec::AnyLenCodec;
+use crate::codec::{AnyLenCodec, encode_with_bound, le_bytes};
 use crate::helpers::AsUsize;
-use crate::rust::cursor::IncrementCursor;
-use crate::{FastPForError, FastPForResult};
+use crate::rust::cursor::Cursor;
+use crate::rust::stream_info::StreamInfo;
+use crate::{Element, FastPForError, FastPForResult};
 
 /// Variable-byte encoding codec for integer compression.
+///
+/// Implements [`AnyLenCodec<T>`] for every [`Element`] type: values are widened
+/// on encode, and decoded straight into `Vec<T>` with a range check. Values
+/// above `u32::MAX` (only possible for 64-bit elements) continue the same 7-bit
+/// scheme for up to 10 bytes, so any value below 2<sup>32</sup> is encoded
+/// identically whatever the element type.
 #[derive(Debug, Default)]
 pub struct VariableByte;
 
@@ -32,12 +39,33 @@ impl VariableByte {
         Self
     }
 
+    /// Counts the `u32` values in `input` without decoding them into a buffer.
+    ///
+    /// Any words decode as `u32` values: the fifth byte of a value only adds its
+    /// low 4 bits, and a value cut short by the end of the input is dropped, as
+    /// when decoding. So, unlike the other codecs' `validate`, this cannot fail
+    /// and returns the [`StreamInfo`] directly; it gives the composites'
+    /// `validate` the tail's value count. The whole input is consumed.
+    #[must_use]
+    #[expect(
+        clippy::unused_self,
+        reason = "a method, like `validate` on the other codecs"
+    )]
+    pub fn validate(&self, input: &[u32]) -> StreamInfo {
+        let mut bytes = WordBytes::new(input);
+        let mut value_count = 0;
+        while Self::decode_value::<u32>(&mut bytes).is_some() {
+            value_count += 1;
+        }
+        StreamInfo::new(value_count, 0, input.len())
+    }
+
     /// Compress `input_length` u32 values from `input[input_offset..]` into
     /// `output[output_offset..]` as packed variable-byte u8 values (stored in
     /// u32 words, padded to 4-byte alignment with `0xFF`).
     #[allow(clippy::unnecessary_wraps)]
-    fn compress_into_slice(
-        input: &[u32],
+    fn compress_into_slice<T: Element>(
+        input: &[T],
         input_length: u32,
         input_offset: &mut Cursor<u32>,
         output: &mut [u32],
@@ -53,7 +81,11 @@ impl VariableByte {
         // Lemire format: last byte has high bit set (c >= 128 means end of value).
         let mut byte_pos = 0;
         for k in input_offset.position()..(input_offset.position() + u64::from(input_length)) {
-            let val = input[k as usize];
+            let val = input[k as usize].to_u64();
+            let Ok(val) = u32::try_from(val) else {
+                byte_pos += Self::compress_wide(val, &mut output_bytes[byte_pos..]);
+                continue;
+            };
             if val < (1 << 7) {
                 output_bytes[byte_pos] = Self::extract_7bits::<0>(val) | (1 << 7);
                 byte_pos += 1;
@@ -88,6 +120,12 @@ impl VariableByte {
             byte_pos += 1;
         }
 
+        // The bytes were written in stream order; store each word as their
+        // little-endian value so that the format does not depend on the host.
+        for word in &mut output[output_start..output_start + byte_pos / 4] {
+            *word = u32::from_le(*word);
+        }
+
         output_offset.add(byte_pos as u32 / 4);
         input_offset.add(input_length);
 
@@ -96,12 +134,36 @@ impl VariableByte {
 
     /// Decompress `input_length` u32 words of variable-byte data from
     /// `input[input_offset..]` into `output[output_offset..]`.
-    fn decompress_from_u32_slice(
+    fn decompress_from_u32_slice<T: Element>(
         input: &[u32],
         input_length: u32,
         input_offset: &mut Cursor<u32>,
-        output: &mut [u32],
+        output: &mut [T],
         output_offset: &mut Cursor<u32>,
+    ) -> FastPForResult<()> {
+        let mut tmp_outpos = output_offset.position() as usize;
+        Self::decompress_values(input, input_length, input_offset, |value| {
+            let dst = output
+                .get_mut(tmp_outpos)
+                .ok_or(FastPForError::OutputBufferTooSmall)?;
+            *dst = value;
+            tmp_outpos += 1;
+            Ok(())
+        })?;
+        output_offset.set_position(tmp_outpos as u64);
+        Ok(())
+    }
+
+    /// Decompress `input_length` u32 words of variable-byte data from
+    /// `input[input_offset..]`, passing each value to `emit` in order.
+    ///
+    /// Lets [`decode`](AnyLenCodec::decode) push straight into a `Vec` instead of
+    /// zero-filling a worst-case sized buffer first.
+    fn decompress_values<T: Element>(
+        input: &[u32],
+        input_length: u32,
+        input_offset: &mut Cursor<u32>,
+        mut emit: impl FnMut(T) -> FastPForResult<()>,
     ) -> FastPForResult<()> {
         if input_length == 0 {
             return Ok(());
@@ -110,11 +172,16 @@ impl VariableByte {
         let byte_length = input_length.as_usize() * 4;
         let input_start = input_offset.position() as usize;
 
-        let input_bytes: &[u8] =
-            &cast_slice::<u32, u8>(input)[input_start * 4..input_start * 4 + byte_length];
+        let input_bytes = le_bytes(&input[input_start..input_start + input_length.as_usize()]);
+        let input_bytes: &[u8] = &input_bytes;
+
+        if T::LANES > 1 {
+            Self::decompress_wide(input_bytes, emit)?;
+            input_offset.add(input_length);
+            return Ok(());
+        }
 
         let mut byte_pos = 0;
-        let mut tmp_outpos = output_offset.position() as usize;
 
         // Lemire format: high bit set (c >= 128) means last byte of value.
         // Fast path: process while we have at least 10 bytes remaining
@@ -139,11 +206,7 @@ impl VariableByte {
             }
 
             byte_pos += bytes_read;
-            if tmp_outpos >= output.len() {
-                return Err(FastPForError::OutputBufferTooSmall);
-            }
-            output[tmp_outpos] = v;
-            tmp_outpos += 1;
+            emit(Self::narrow(u64::from(v))?)?;
         }
 
         // Slow path: process remaining bytes (lemire: c >= 128 = last byte)
@@ -169,20 +232,84 @@ impl VariableByte {
                 }
             }
             if decoded {
-                if tmp_outpos >= output.len() {
-                    return Err(FastPForError::OutputBufferTooSmall);
-                }
-                output[tmp_outpos] = v;
-                tmp_outpos += 1;
+                emit(Self::narrow(u64::from(v))?)?;
             }
         }
 
-        output_offset.set_position(tmp_outpos as u64);
         input_offset.add(input_length);
 
         Ok(())
     }
 
+    /// Encodes a value above `u32::MAX` into `output`, returning the number of bytes written.
+    fn compress_wide(mut val: u64, output: &mut [u8]) -> usize {
+        let mut len = 0;
+        while val >= (1 << 7) {
+            output[len] = (val & ((1 << 7) - 1)) as u8;
+            val >>= 7;
+            len += 1;
+        }
+        output[len] = val as u8 | (1 << 7);
+        len + 1
+    }
+
+    /// Like [`decompress_values`](Self::decompress_values), for 64-bit
+    /// elements whose values may span up to 10 bytes.
+    fn decompress_wide<T: Element>(
+        input_bytes: &[u8],
+        mut emit: impl FnMut(T) -> FastPForResult<()>,
+    ) -> FastPForResult<()> {
+        let mut v: u64 = 0;
+        let mut shift = 0;
+        for &c in input_bytes {
+            v |= u64::from(c & 0x7F) << shift;
+            // The 10th byte always ends the value (7*9 + 1 = 64 bits), mirroring
+            // how the 5th byte ends a 32-bit value.
+            if c >= 128 || shift == 63 {
+                emit(Self::narrow(v)?)?;
+                v = 0;
+                shift = 0;
+            } else {
+                shift += 7;
+            }
+        }
+        Ok(())
+    }
+
+    /// Decodes the next value from `bytes`, consuming its bytes.
+    ///
+    /// Returns `None` once the input is exhausted, including when only the zero
+    /// padding after the last value is left.
+    pub(crate) fn decode_value<T: Element>(
+        bytes: &mut impl Iterator<Item = u8>,
+    ) -> Option<FastPForResult<T>> {
+        let max_bytes = if T::LANES > 1 { 10 } else { 5 };
+        let mut v: u64 = 0;
+        for (i, c) in bytes.take(max_bytes).enumerate() {
+            if T::LANES == 1 && i == 4 {
+                // 5th byte: only 4 bits contribute (7*4 bits already used)
+                v |= u64::from(c & 0x0F) << 28;
+            } else {
+                v |= u64::from(c & 0x7F) << (7 * i);
+            }
+            if c >= 128 || i + 1 == max_bytes {
+                return Some(Self::narrow(v));
+            }
+        }
+        None
+    }
+
+    /// Returns the input length as a `u32`, the limit of a single encode call.
+    fn input_length<T: Element>(input: &[T]) -> FastPForResult<u32> {
+        u32::try_from(input.len()).map_err(|_| FastPForError::InvalidInputLength(input.len()))
+    }
+
+    /// Converts a decoded value to the output element type.
+    #[inline]
+    fn narrow<T: Element>(value: u64) -> FastPForResult<T> {
+        T::from_u64(value).ok_or(FastPForError::ValueOutOfRange(value))
+    }
+
     /// Compress `input_length` u32 values into an `i8` slice using sign-bit
     /// continuation encoding (negative i8 = more bytes follow).
     #[cfg(test)]
@@ -301,54 +428,102 @@ impl VariableByte {
     }
 }
 
-impl AnyLenCodec for VariableByte {
-    fn encode(&mut self, input: &[u32], out: &mut Vec<u32>) -> FastPForResult<()> {
-        let capacity = input.len() * 2 + 4;
-        let start = out.len();
-        out.resize(start + capacity, 0);
-        let mut in_off = Cursor::new(0u32);
+/// The bytes of little-endian `u32` words in stream order, read one at a time
+/// without copying the words.
+#[derive(Debug, Clone)]
+pub(crate) struct WordBytes<'a> {
+    words: &'a [u32],
+    pos: usize,
+}
+
+impl<'a> WordBytes<'a> {
+    pub(crate) fn new(words: &'a [u32]) -> Self {
+        Self { words, pos: 0 }
+    }
+}
+
+impl Iterator for WordBytes<'_> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.words.get(self.pos / 4)?.to_le_bytes()[self.pos % 4];
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.words.len() * 4 - self.pos;
+        (n, Some(n))
+    }
+}
+
+impl<T: Element> AnyLenCodec<T> for VariableByte {
+    fn encode(&mut self, input: &[T], out: &mut Vec<u32>) -> FastPForResult<()> {
+        // Check the length first, so that oversized input fails before allocating.
+        Self::input_length(input)?;
+        let bound = <Self as AnyLenCodec<T>>::max_compressed_len(self, input.len());
+        encode_with_bound(out, bound, |out| self.encode_into(input, out))
+    }
+
+    /// Every value takes at most 5 bytes per `u32` lane, padded to whole words.
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        (n_values * 5 * T::LANES).div_ceil(4)
+    }
+
+    fn encode_into(&mut self, input: &[T], out: &mut [u32]) -> FastPForResult<usize> {
+        let input_length = Self::input_length(input)?;
+        if out.len() < <Self as AnyLenCodec<T>>::max_compressed_len(self, input.len()) {
+            return Err(FastPForError::OutputBufferTooSmall);
+        }
         let mut out_off = Cursor::new(0u32);
         Self::compress_into_slice(
             input,
-            input.len() as u32,
-            &mut in_off,
-            &mut out[start..],
+            input_length,
+            &mut Cursor::new(0u32),
+            out,
             &mut out_off,
         )?;
-        let written = out_off.position() as usize;
-        out.truncate(start + written);
-        Ok(())
+        Ok(out_off.position() as usize)
     }
 
     fn decode(
         &mut self,
         input: &[u32],
-        out: &mut Vec<u32>,
+        out: &mut Vec<T>,
         expected_len: Option<u32>,
     ) -> FastPForResult<()> {
         let capacity = if let Some(expected) = expected_len {
-            expected.is_valid_expected(Self::max_decompressed_len(input.len()))?
+            expected
+                .is_valid_expected(<Self as AnyLenCodec<T>>::max_decompressed_len(input.len()))?
         } else {
             input.len() * 4
         };
         let start = out.len();
         out.reserve(capacity);
-        out.resize(start + capacity, 0);
-        let mut in_off = Cursor::new(0u32);
+        Self::decompress_values(input, input.len() as u32, &mut Cursor::new(0u32), |value| {
+            out.push(value);
+            Ok(())
+        })?;
+        let written = out.len() - start;
+        if let Some(n) = expected_len {
+            written.is_decoded_mismatch(n)?;
+        }
+        Ok(())
+    }
+
+    fn decode_into(&mut self, input: &[u32], out: &mut [T]) -> FastPForResult<usize> {
+        let input_length = u32::try_from(input.len())
+            .map_err(|_| FastPForError::InvalidInputLength(input.len()))?;
         let mut out_off = Cursor::new(0u32);
         Self::decompress_from_u32_slice(
             input,
-            input.len() as u32,
-            &mut in_off,
-            &mut out[start..],
+            input_length,
+            &mut Cursor::new(0u32),
+            out,
             &mut out_off,
         )?;
-        let written = out_off.position() as usize;
-        out.truncate(start + written);
-        if let Some(n) = expected_len {
-            written.is_decoded_mismatch(n)?;
-        }
-        Ok(())
+        Ok(out_off.position() as usize)
     }
 }
 
@@ -358,7 +533,7 @@ mod tests {
     use std::hash::{BuildHasher, Hasher};
 
     use super::*;
-    use crate::test_utils::{compress, decompress, roundtrip};
+    use crate::test_utils::{compress, decompress, roundtrip, roundtrip_elements, roundtrip_into};
 
     fn verify_u32_roundtrip(input: &[u32]) {
         let mut encoded: Vec<u32> = vec![0; input.len() * 2 + 1];
@@ -635,7 +810,7 @@ mod tests {
         let encoded_len = out_off.position() as u32;
 
         // Zero-capacity output — must error.
-        let result = VariableByte::decompress_from_u32_slice(
+        let result = VariableByte::decompress_from_u32_slice::<u32>(
             &encoded,
             encoded_len,
             &mut Cursor::new(0u32),
@@ -663,4 +838,98 @@ mod tests {
         let encoded = compress::<VariableByte>(&[1u32, 2, 3]).unwrap();
         decompress::<VariableByte>(&encoded, Some(10)).unwrap_err();
     }
+
+    #[test]
+    fn test_narrow_elements_roundtrip() {
+        roundtrip_elements::<u8, VariableByte>(&[0, 1, 127, 128, 255]);
+        roundtrip_elements::<u16, VariableByte>(&[0, 127, 128, 16383, 16384, u16::MAX]);
+    }
+
+    #[test]
+    fn test_narrow_decode_rejects_out_of_range_values() {
+        let encoded = compress::<VariableByte>(&[1u32, 300]).unwrap();
+        let mut decoded: Vec<u8> = Vec::new();
+        let err = VariableByte
+            .decode(&encoded, &mut decoded, None)
+            .unwrap_err();
+        assert!(matches!(err, FastPForError::ValueOutOfRange(300)));
+    }
+
+    #[test]
+    fn test_wide_elements_roundtrip() {
+        let values = [0, 127, 1 << 32, (1 << 35) - 1, 1 << 35, 1 << 63, u64::MAX];
+        roundtrip_elements::<u64, VariableByte>(&values);
+        roundtrip_elements::<i64, VariableByte>(&[0, -1, i64::MIN, i64::MAX, 42]);
+        roundtrip_elements::<i32, VariableByte>(&[0, -1, i32::MIN, i32::MAX, 42]);
+    }
+
+    #[test]
+    fn test_wide_encoding_matches_u32_for_small_values() {
+        let narrow: Vec<u32> = vec![0, 300, 70_000, u32::MAX];
+        let wide: Vec<u64> = narrow.iter().map(|&v| u64::from(v)).collect();
+        let mut encoded = Vec::new();
+        VariableByte.encode(&wide, &mut encoded).unwrap();
+        assert_eq!(encoded, compress::<VariableByte>(&narrow).unwrap());
+    }
+
+    #[test]
+    fn test_decode_into_slice() {
+        roundtrip_into::<u32, VariableByte>(&[0, 127, 128, 16_384, u32::MAX]);
+        roundtrip_into::<u64, VariableByte>(&[1, u64::MAX, 1 << 40]);
+        roundtrip_into::<u16, VariableByte>(&[]);
+    }
+
+    #[test]
+    fn test_encode_into_exact_worst_case() {
+        let data = [u32::MAX; 7];
+        let bound = AnyLenCodec::<u32>::max_compressed_len(&VariableByte, data.len());
+        assert_eq!(bound, 9);
+        let mut out = vec![0; bound];
+        assert_eq!(VariableByte.encode_into(&data, &mut out).unwrap(), bound);
+        let err = VariableByte
+            .encode_into(&data, &mut out[..bound - 1])
+            .unwrap_err();
+        assert!(matches!(err, FastPForError::OutputBufferTooSmall));
+
+        crate::test_utils::encode_into_matches::<u64, VariableByte>(&[u64::MAX, 0, 1 << 35]);
+    }
+
+    #[test]
+    fn test_words_are_little_endian_on_every_host() {
+        // 1 -> [0x81], 300 -> [0x2C, 0x82], then one byte of padding.
+        let encoded = compress::<VariableByte>(&[1, 300]).unwrap();
+        assert_eq!(encoded, [u32::from_le_bytes([0x81, 0x2C, 0x82, 0x00])]);
+
+        // A big-endian host holds the same word value, so decoding it reads the
+        // bytes back in stream order too.
+        assert_eq!(
+            decompress::<VariableByte>(&encoded, None).unwrap(),
+            [1, 300]
+        );
+        let iter = VariableByte::decode_iter::<u32>(&encoded);
+        assert_eq!(iter.collect::<FastPForResult<Vec<_>>>().unwrap(), [1, 300]);
+    }
+
+    #[test]
+    fn test_word_bytes_reads_stream_order() {
+        let words = [
+            u32::from_le_bytes([1, 2, 3, 4]),
+            u32::from_le_bytes([5, 6, 7, 8]),
+        ];
+        let mut bytes = WordBytes::new(&words);
+        assert_eq!(bytes.size_hint(), (8, Some(8)));
+        assert_eq!(bytes.by_ref().take(3).collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(bytes.size_hint(), (5, Some(5)));
+        assert_eq!(bytes.collect::<Vec<_>>(), [4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_validate_counts_values() {
+        let input = [1, 300, u32::MAX, 0, 1 << 21];
+        let encoded = compress::<VariableByte>(&input).unwrap();
+        let info = VariableByte::new().validate(&encoded);
+        assert_eq!(info.value_count(), input.len());
+        assert_eq!(info.consumed_words(), encoded.len());
+        assert_eq!(VariableByte::new().validate(&[]).value_count(), 0);
+    }
 }
@@ -0,0 +1,299 @@
+//! [`JavaFastPFor`] and [`JavaVariableByte`]: read and write the `int[]`
+//! streams of [JavaFastPFOR](https://github.com/lemire/JavaFastPFOR).
+//!
+//! `JavaFastPFOR` writes the same words as the C++ library, except that:
+//!
+//! - bytes are packed into `int`s through a big-endian `ByteBuffer`, so the
+//!   `FastPFOR` per-block metadata words and the `VariableByte` words hold
+//!   their bytes in the opposite order;
+//! - an empty input is encoded as no words at all, not as a `[0]` header.
+//!
+//! Both codecs convert between the two layouts around the Rust codecs.
+
+use alloc::vec::Vec;
+
+use crate::codec::AnyLenCodec;
+use crate::helpers::{AsUsize, GetWithErr};
+use crate::rust::integer_compression::fastpfor::sealed::BlockSize;
+use crate::rust::{CompositeCodec, FastPFor, VariableByte};
+use crate::{FastPForError, FastPForResult};
+
+/// [`JavaFastPFor`] with 256-value blocks, like Java's `FastPFOR`.
+pub type JavaFastPFor256 = JavaFastPFor<256>;
+
+/// [`JavaFastPFor`] with 128-value blocks, like Java's `FastPFOR128`.
+pub type JavaFastPFor128 = JavaFastPFor<128>;
+
+/// Reads and writes the output of `JavaFastPFOR`'s
+/// `Composition(new FastPFOR(), new VariableByte())`, or of `FastPFOR128` with
+/// `N = 128`.
+///
+/// Java `int`s are read and written as `u32` bit patterns. The Java codec
+/// always uses the default page size of 65536 values and has no checksums.
+///
+/// The packed exceptions of a page end in a partly used word. Java fills its
+/// unused bits with leftovers from earlier pages, where this codec writes
+/// zeros, so streams of more than one page can differ in those bits; both
+/// decoders ignore them.
+///
+/// # Example
+///
+/// ```
+/// use fastpfor::{AnyLenCodec, JavaFastPFor256};
+///
+/// // `new Composition(new FastPFOR(), new VariableByte())` output for {1, 300, 5}.
+/// let java = [0, 0x812C_8285];
+/// let mut decoded = Vec::new();
+/// JavaFastPFor256::default().decode(&java, &mut decoded, None).unwrap();
+/// assert_eq!(decoded, [1, 300, 5]);
+/// ```
+#[derive(Default)]
+pub struct JavaFastPFor<const N: usize>
+where
+    [u32; N]: BlockSize,
+{
+    codec: CompositeCodec<FastPFor<N>, VariableByte>,
+    /// Input converted to the C++ layout, reused between decodes
+    words: Vec<u32>,
+}
+
+impl<const N: usize> AnyLenCodec for JavaFastPFor<N>
+where
+    [u32; N]: BlockSize,
+{
+    fn encode(&mut self, input: &[u32], out: &mut Vec<u32>) -> FastPForResult<()> {
+        if input.is_empty() {
+            return Ok(());
+        }
+        let start = out.len();
+        self.codec.encode(input, out)?;
+        let page_values = self.codec.block.values_per_page();
+        swap_java_bytes(&mut out[start..], page_values)
+    }
+
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        self.codec.max_compressed_len(n_values)
+    }
+
+    fn page_len(&self) -> Option<usize> {
+        self.codec.page_len()
+    }
+
+    fn max_decompressed_len(compressed_words: usize) -> usize {
+        <CompositeCodec<FastPFor<N>, VariableByte>>::max_decompressed_len(compressed_words)
+    }
+
+    fn decode(
+        &mut self,
+        input: &[u32],
+        out: &mut Vec<u32>,
+        expected_len: Option<u32>,
+    ) -> FastPForResult<()> {
+        self.words.clear();
+        self.words.extend_from_slice(input);
+        if !input.is_empty() {
+            let page_values = self.codec.block.values_per_page();
+            swap_java_bytes(&mut self.words, page_values)?;
+        }
+        self.codec.decode(&self.words, out, expected_len)
+    }
+}
+
+/// Reads and writes the output of `JavaFastPFOR`'s `VariableByte`.
+///
+/// Values use the same bytes as [`VariableByte`], packed four to a word in
+/// big-endian order and padded with zero bytes. Java `int`s are read and
+/// written as `u32` bit patterns.
+///
+/// ```
+/// use fastpfor::{AnyLenCodec, JavaVariableByte};
+///
+/// let mut encoded = Vec::new();
+/// JavaVariableByte::default().encode(&[1, 300], &mut encoded).unwrap();
+/// assert_eq!(encoded, [0x812C_8200]);
+/// ```
+#[derive(Debug, Default)]
+pub struct JavaVariableByte {
+    /// Input converted to the C++ layout, reused between decodes
+    words: Vec<u32>,
+}
+
+impl AnyLenCodec for JavaVariableByte {
+    fn encode(&mut self, input: &[u32], out: &mut Vec<u32>) -> FastPForResult<()> {
+        let start = out.len();
+        VariableByte::new().encode(input, out)?;
+        swap_words(&mut out[start..]);
+        Ok(())
+    }
+
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        AnyLenCodec::<u32>::max_compressed_len(&VariableByte::new(), n_values)
+    }
+
+    fn max_decompressed_len(compressed_words: usize) -> usize {
+        <VariableByte as AnyLenCodec>::max_decompressed_len(compressed_words)
+    }
+
+    fn decode(
+        &mut self,
+        input: &[u32],
+        out: &mut Vec<u32>,
+        expected_len: Option<u32>,
+    ) -> FastPForResult<()> {
+        self.words.clear();
+        self.words.extend_from_slice(input);
+        swap_words(&mut self.words);
+        VariableByte::new().decode(&self.words, out, expected_len)
+    }
+}
+
+/// Reverses the bytes of every word in `words`.
+fn swap_words(words: &mut [u32]) {
+    for word in words {
+        *word = word.swap_bytes();
+    }
+}
+
+/// Converts a non-empty `FastPFor` + `VariableByte` stream between the C++ and
+/// the Java layout, in either direction: reverses the bytes of every page's
+/// metadata words and of the `VariableByte` tail.
+///
+/// Only the page offsets, metadata byte counts and exception section sizes are
+/// read, and none of them is affected by the conversion.
+fn swap_java_bytes(words: &mut [u32], page_values: usize) -> FastPForResult<()> {
+    let mut remaining = words.get_val(0usize)?.as_usize();
+    let mut pos = 1usize;
+    while remaining > 0 {
+        let meta = pos.saturating_add(words.get_val(pos)?.as_usize());
+        let meta_words = words.get_val(meta)?.as_usize().div_ceil(4);
+        let bitmap_pos = meta + 1 + meta_words;
+        let bitmap = words.get_val(bitmap_pos)?;
+        swap_words(&mut words[meta + 1..bitmap_pos]);
+
+        pos = bitmap_pos + 1;
+        for width in 2..=32usize {
+            if bitmap & (1 << (width - 1)) != 0 {
+                let count = words.get_val(pos)?.as_usize();
+                pos += 1 + (count * width).div_ceil(32);
+            }
+        }
+        remaining = remaining.saturating_sub(page_values);
+    }
+    let tail = words.get_mut(pos..).ok_or(FastPForError::NotEnoughData)?;
+    swap_words(tail);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FastPFor128;
+    use crate::test_utils::{compress, decompress};
+
+    // `JavaFastPFOR` output for `pages_data`, `{1, 300}` and (through
+    // `VariableByte` alone) `{1, 300, 5, 1}`, written by
+    // `tests/java/GoldenVectors.java`; its header has the invocation.
+    include!("../../tests/java/golden.rs");
+
+    /// Two full pages and a partial one, with exceptions of other widths in
+    /// each page, then a `VariableByte` tail. Same as `pagesData` in
+    /// `GoldenVectors.java`.
+    fn pages_data() -> Vec<u32> {
+        let len = 2 * 65536 + 40_000 + 67;
+        let tail_start = len / 256 * 256;
+        (0..len)
+            .map(|i| {
+                let (block, pos, page) = (i / 256, i % 256, i / 65536);
+                if i >= tail_start {
+                    i * 37 % 100_000
+                } else if block % 3 == 0 {
+                    let width = 2 + 10 * page + block / 3 % 10 + u32::from(page == 2);
+                    let high = 1 << (width - 1);
+                    if pos == 7 { high | (i & (high - 1)) } else { 0 }
+                } else if block % 3 == 1 {
+                    if block % 30 == 1 { i % 5 } else { 0 }
+                } else {
+                    u32::from(pos == 100)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_golden_pages() {
+        let data = pages_data();
+        let mut codec = JavaFastPFor256::default();
+        let mut encoded = Vec::new();
+        codec.encode(&data, &mut encoded).unwrap();
+        assert_eq!(encoded, GOLDEN_PAGES);
+
+        let mut decoded = Vec::new();
+        codec
+            .decode(&GOLDEN_PAGES, &mut decoded, Some(data.len() as u32))
+            .unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_golden_tail_only_and_empty() {
+        // Java's `FastPFOR` writes nothing for fewer than 256 values, so the
+        // composition writes the `0` header itself.
+        let mut codec = JavaFastPFor256::default();
+        let mut encoded = Vec::new();
+        codec.encode(&[1, 300], &mut encoded).unwrap();
+        assert_eq!(encoded, GOLDEN_TAIL_ONLY);
+        let mut decoded = Vec::new();
+        codec.decode(&GOLDEN_TAIL_ONLY, &mut decoded, None).unwrap();
+        assert_eq!(decoded, [1, 300]);
+
+        // An empty input has no header at all.
+        encoded.clear();
+        codec.encode(&[], &mut encoded).unwrap();
+        assert!(encoded.is_empty());
+        decoded.clear();
+        codec.decode(&[], &mut decoded, None).unwrap();
+        assert!(decoded.is_empty());
+    }
+
+    #[test]
+    fn test_golden_variable_byte() {
+        let mut codec = JavaVariableByte::default();
+        let mut encoded = Vec::new();
+        codec.encode(&[1, 300, 5, 1], &mut encoded).unwrap();
+        assert_eq!(encoded, GOLDEN_VARIABLE_BYTE);
+
+        let mut decoded = Vec::new();
+        codec
+            .decode(&GOLDEN_VARIABLE_BYTE, &mut decoded, Some(4))
+            .unwrap();
+        assert_eq!(decoded, [1, 300, 5, 1]);
+    }
+
+    #[test]
+    fn test_pages_match_cpp_layout_after_swap() {
+        // Several pages with exceptions of many widths, then a tail.
+        let data: Vec<u32> = (0..200_000u32)
+            .map(|i| i.wrapping_mul(2_654_435_761) >> (i % 29))
+            .collect();
+        let mut java = Vec::new();
+        JavaFastPFor128::default().encode(&data, &mut java).unwrap();
+        let cpp = compress::<FastPFor128>(&data).unwrap();
+        assert_eq!(java.len(), cpp.len());
+        assert_ne!(java, cpp);
+
+        let mut converted = java.clone();
+        swap_java_bytes(&mut converted, 65536).unwrap();
+        assert_eq!(converted, cpp);
+        assert_eq!(decompress::<JavaFastPFor128>(&java, None).unwrap(), data);
+    }
+
+    #[test]
+    fn test_truncated_stream_errors() {
+        for len in [1, 5, 200, 800] {
+            assert!(matches!(
+                JavaFastPFor256::default().decode(&GOLDEN_PAGES[..len], &mut Vec::new(), None),
+                Err(FastPForError::NotEnoughData)
+            ));
+        }
+    }
+}
@@ -1,14 +1,35 @@
+mod appender;
 mod composite;
+mod crc32c;
 mod cursor;
+mod decode_iter;
+mod inspect;
 mod integer_compression;
+mod java;
+mod seekable;
+mod stream_info;
 
+/// Continues an encoded `FastPFor` composite stream.
+pub use appender::{Appender, FastPFor128Appender, FastPFor256Appender};
 pub use composite::CompositeCodec;
+/// Lazy decoding iterators returned by `decode_iter`.
+pub use decode_iter::{FastPForIter, VariableByteIter};
+/// Per-page and per-block details returned by `FastPFor::inspect`.
+pub use inspect::{BlockInfo, ExceptionWidth, PageInfo};
 /// Type-safe block codec with block size encoded in the type.
-pub use integer_compression::fastpfor::{FastPFor, FastPForBlock128, FastPForBlock256};
+pub use integer_compression::fastpfor::{
+    FastPFor, FastPForBlock128, FastPForBlock256, FastPForConfig, FastPForScratch,
+};
 /// Pass-through codec — implements [`AnyLenCodec`](crate::codec::AnyLenCodec).
 pub use integer_compression::just_copy::JustCopy;
 /// Variable-byte codec — implements [`AnyLenCodec`](crate::codec::AnyLenCodec).
 pub use integer_compression::variable_byte::VariableByte;
+/// Codecs for the streams of the Java port, `JavaFastPFOR`.
+pub use java::{JavaFastPFor, JavaFastPFor128, JavaFastPFor256, JavaVariableByte};
+/// Random-access `FastPFor` composite with a page index.
+pub use seekable::{SeekableFastPFor, SeekableFastPFor128, SeekableFastPFor256};
+/// Result of validating an encoded stream.
+pub use stream_info::StreamInfo;
 
 /// `FastPForBlock256` blocks + `VariableByte` remainder — the most common composite.
 pub type FastPFor256 = CompositeCodec<FastPForBlock256, VariableByte>;
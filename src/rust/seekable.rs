@@ -0,0 +1,444 @@
+//! [`SeekableFastPFor`]: a `FastPFor` composite with a page index, for random
+//! access without decoding the whole stream.
+
+use alloc::vec::Vec;
+use core::ops::{ControlFlow, Range};
+
+use crate::codec::{AnyLenCodec, slice_to_blocks};
+use crate::helpers::AsUsize;
+use crate::rust::cursor::Cursor;
+use crate::rust::integer_compression::fastpfor::sealed::BlockSize;
+use crate::rust::{CompositeCodec, FastPFor, FastPForConfig, VariableByte};
+use crate::{FastPForError, FastPForResult};
+
+/// Number of `u32` words before the page offsets.
+const HEADER_WORDS: usize = 2;
+
+/// [`SeekableFastPFor`] with 256-element blocks.
+pub type SeekableFastPFor256 = SeekableFastPFor<256>;
+
+/// [`SeekableFastPFor`] with 128-element blocks.
+pub type SeekableFastPFor128 = SeekableFastPFor<128>;
+
+/// A `FastPFor` + `VariableByte` composite that also stores where each page
+/// starts, so that single values or ranges can be decoded on their own.
+///
+/// [`get`](Self::get) and [`decode_range`](Self::decode_range) jump straight to
+/// the pages that hold the requested values. Within a page, blocks before the
+/// range are skipped using their metadata, only the touched blocks are
+/// unpacked, and exceptions used only by later blocks are left packed.
+///
+/// # Wire format
+///
+/// ```text
+/// [ n_values: u32 ] [ page_count: u32 ]
+/// [ page offsets: (page_count + 1) × u32 ]
+/// [ FastPFor256-style stream: [ block values ][ pages... ][ VariableByte tail ] ]
+/// ```
+///
+/// Offsets are relative to the start of the embedded stream; the last one is
+/// the start of the tail. The embedded stream is exactly what the matching
+/// [`CompositeCodec`] writes, so the index adds `page_count + 3` words. Like
+/// the other `FastPFor` codecs, a stream must be read with the page size it
+/// was written with.
+///
+/// # Example
+///
+/// ```
+/// use fastpfor::{AnyLenCodec, SeekableFastPFor256};
+///
+/// let data: Vec<u32> = (0..1_000_000).map(|i| i % 1000).collect();
+/// let mut codec = SeekableFastPFor256::default();
+/// let mut encoded = Vec::new();
+/// codec.encode(&data, &mut encoded).unwrap();
+///
+/// assert_eq!(codec.get(&encoded, 765_432).unwrap(), 432);
+///
+/// let mut values = Vec::new();
+/// codec.decode_range(&encoded, 999_900..1_000_000, &mut values).unwrap();
+/// assert_eq!(values, data[999_900..]);
+/// ```
+pub struct SeekableFastPFor<const N: usize> {
+    inner: CompositeCodec<FastPFor<N>, VariableByte>,
+}
+
+impl<const N: usize> Default for SeekableFastPFor<N>
+where
+    [u32; N]: BlockSize,
+{
+    fn default() -> Self {
+        Self::from_config(FastPForConfig::default())
+    }
+}
+
+/// Parsed index of a seekable stream.
+struct Index<'a> {
+    n_values: usize,
+    /// Values in the pages, i.e. before the tail
+    block_values: usize,
+    /// `page_count + 1` offsets into `stream`
+    offsets: &'a [u32],
+    stream: &'a [u32],
+}
+
+impl<const N: usize> SeekableFastPFor<N>
+where
+    [u32; N]: BlockSize,
+{
+    /// Creates a codec with the given `FastPFor` configuration.
+    #[must_use]
+    pub fn from_config(config: FastPForConfig<N>) -> Self {
+        Self {
+            inner: CompositeCodec::new(FastPFor::from_config(config), VariableByte::new()),
+        }
+    }
+
+    /// Number of values in an encoded stream, read from its header.
+    pub fn value_count(input: &[u32]) -> FastPForResult<usize> {
+        input
+            .first()
+            .map(|&n| n.as_usize())
+            .ok_or(FastPForError::NotEnoughData)
+    }
+
+    /// Decodes the value at `index`.
+    pub fn get(&mut self, input: &[u32], index: usize) -> FastPForResult<u32> {
+        let range = index..index.saturating_add(1);
+        let index = self.index_for(input, &range)?;
+        let mut value = 0;
+        self.visit_range(&index, range, &mut |values| {
+            value = values[0];
+            Ok(())
+        })?;
+        Ok(value)
+    }
+
+    /// Decodes the values in `range`, appending them to `out`.
+    ///
+    /// Returns [`FastPForError::RangeOutOfBounds`] if `range` does not lie
+    /// within the stream.
+    pub fn decode_range(
+        &mut self,
+        input: &[u32],
+        range: Range<usize>,
+        out: &mut Vec<u32>,
+    ) -> FastPForResult<()> {
+        let index = self.index_for(input, &range)?;
+        // Only now is `range` known to lie within the stream.
+        out.reserve(range.len());
+        self.visit_range(&index, range, &mut |values| {
+            out.extend_from_slice(values);
+            Ok(())
+        })
+    }
+
+    /// Parses the index of `input` and checks that `range` lies within the
+    /// stream.
+    fn index_for<'a>(&self, input: &'a [u32], range: &Range<usize>) -> FastPForResult<Index<'a>> {
+        let index = Self::parse(input, self.inner.block.values_per_page())?;
+        if range.start > range.end || range.end > index.n_values {
+            return Err(FastPForError::RangeOutOfBounds {
+                start: range.start,
+                end: range.end,
+                len: index.n_values,
+            });
+        }
+        Ok(index)
+    }
+
+    /// Passes the values in `range`, which [`index_for`](Self::index_for)
+    /// has checked, in order and in one or more slices, to `emit`.
+    fn visit_range(
+        &mut self,
+        index: &Index<'_>,
+        range: Range<usize>,
+        emit: &mut impl FnMut(&[u32]) -> FastPForResult<()>,
+    ) -> FastPForResult<()> {
+        let page_values = self.inner.block.values_per_page();
+
+        let block_range = range.start.min(index.block_values)..range.end.min(index.block_values);
+        if !block_range.is_empty() {
+            let first_page = block_range.start / page_values;
+            let last_page = (block_range.end - 1) / page_values;
+            for page in first_page..=last_page {
+                let page_start = page * page_values;
+                let this_size = page_values.min(index.block_values - page_start);
+                // Value range within this page, then the blocks that cover it.
+                let lo = block_range.start.max(page_start) - page_start;
+                let hi = block_range.end.min(page_start + this_size) - page_start;
+                let blocks = (lo / N) as u32..hi.div_ceil(N) as u32;
+
+                let mut block_start = page_start + blocks.start as usize * N;
+                let mut in_off = Cursor::new(index.offsets[page]);
+                let _ = self.inner.block.decode_page_blocks(
+                    index.stream,
+                    &mut in_off,
+                    this_size as u32,
+                    page,
+                    blocks,
+                    &mut |block| {
+                        let from = range.start.saturating_sub(block_start).min(N);
+                        let to = range.end.saturating_sub(block_start).min(N);
+                        block_start += N;
+                        emit(&block[from..to])?;
+                        Ok(ControlFlow::Continue(()))
+                    },
+                )?;
+            }
+        }
+
+        if range.end > index.block_values && !range.is_empty() {
+            let tail_start = index.offsets[index.offsets.len() - 1].as_usize();
+            let tail_input = index
+                .stream
+                .get(tail_start..)
+                .ok_or(FastPForError::NotEnoughData)?;
+            let mut tail = Vec::new();
+            VariableByte::new().decode(tail_input, &mut tail, None)?;
+            let from = range.start.saturating_sub(index.block_values);
+            let to = range.end - index.block_values;
+            emit(tail.get(from..to).ok_or(FastPForError::NotEnoughData)?)?;
+        }
+        Ok(())
+    }
+
+    /// Splits `input` into its header, page offsets and embedded stream, and
+    /// checks that they agree with each other and with `page_values`.
+    fn parse(input: &[u32], page_values: usize) -> FastPForResult<Index<'_>> {
+        let Some((&[n_values, page_count], rest)) = input.split_first_chunk::<HEADER_WORDS>()
+        else {
+            return Err(FastPForError::NotEnoughData);
+        };
+        let Some((offsets, stream)) = rest.split_at_checked(page_count.as_usize() + 1) else {
+            return Err(FastPForError::NotEnoughData);
+        };
+        let block_values = FastPFor::<N>::read_length_header(stream, None)?;
+        let n_values = n_values.as_usize();
+        if block_values > n_values
+            || n_values - block_values >= N
+            || block_values.div_ceil(page_values) != page_count.as_usize()
+        {
+            return Err(FastPForError::NotEnoughData);
+        }
+        Ok(Index {
+            n_values,
+            block_values,
+            offsets,
+            stream,
+        })
+    }
+}
+
+impl<const N: usize> AnyLenCodec<u32> for SeekableFastPFor<N>
+where
+    [u32; N]: BlockSize,
+{
+    fn encode(&mut self, input: &[u32], out: &mut Vec<u32>) -> FastPForResult<()> {
+        let n_values = u32::try_from(input.len())
+            .map_err(|_| FastPForError::InvalidInputLength(input.len()))?;
+        let (blocks, remainder) = slice_to_blocks::<FastPFor<N>>(input);
+        let mut stream = Vec::new();
+        let mut offsets = Vec::new();
+        self.inner
+            .block
+            .encode_blocks_indexed(blocks, &mut stream, &mut offsets)?;
+        VariableByte::new().encode(remainder, &mut stream)?;
+
+        out.reserve(HEADER_WORDS + offsets.len() + stream.len());
+        out.extend([n_values, (offsets.len() - 1) as u32]);
+        out.extend_from_slice(&offsets);
+        out.extend_from_slice(&stream);
+        Ok(())
+    }
+
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        let pages = (n_values / N * N).div_ceil(self.inner.block.values_per_page());
+        HEADER_WORDS + pages + 1 + self.inner.max_compressed_len(n_values)
+    }
+
+    fn page_len(&self) -> Option<usize> {
+        self.inner.page_len()
+    }
+
+    fn decode(
+        &mut self,
+        input: &[u32],
+        out: &mut Vec<u32>,
+        expected_len: Option<u32>,
+    ) -> FastPForResult<()> {
+        if input.is_empty() {
+            return self.inner.decode(input, out, expected_len);
+        }
+        let index = Self::parse(input, self.inner.block.values_per_page())?;
+        if let Some(expected) = expected_len {
+            index.n_values.is_decoded_mismatch(expected)?;
+        }
+        let start = out.len();
+        self.inner
+            .decode(index.stream, out, u32::try_from(index.n_values).ok())?;
+        (out.len() - start).is_decoded_mismatch(index.n_values)
+    }
+
+    fn decode_into(&mut self, input: &[u32], out: &mut [u32]) -> FastPForResult<usize> {
+        if input.is_empty() {
+            return self.inner.decode_into(input, out);
+        }
+        let index = Self::parse(input, self.inner.block.values_per_page())?;
+        let written = self.inner.decode_into(index.stream, out)?;
+        written.is_decoded_mismatch(index.n_values)?;
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FastPFor256;
+    use crate::test_utils::{compress, get_test_cases, roundtrip, roundtrip_into};
+
+    fn encode<const N: usize>(codec: &mut SeekableFastPFor<N>, data: &[u32]) -> Vec<u32>
+    where
+        [u32; N]: BlockSize,
+    {
+        let mut encoded = Vec::new();
+        codec.encode(data, &mut encoded).unwrap();
+        encoded
+    }
+
+    /// Small pages, so that ranges cross page boundaries.
+    fn small_pages() -> SeekableFastPFor128 {
+        SeekableFastPFor::from_config(FastPForConfig::new(256).unwrap())
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for n in [0, 1, 127, 128, 300, 1000] {
+            for data in get_test_cases(n) {
+                roundtrip::<SeekableFastPFor256>(&data);
+                roundtrip_into::<u32, SeekableFastPFor128>(&data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_embeds_composite_stream() {
+        let data: Vec<u32> = (0..70_000).map(|i| i % 777).collect();
+        let encoded = compress::<SeekableFastPFor256>(&data).unwrap();
+        let composite = compress::<FastPFor256>(&data).unwrap();
+        let page_count = encoded[1] as usize;
+        assert_eq!(page_count, 2);
+        assert_eq!(encoded[HEADER_WORDS + page_count + 1..], composite);
+    }
+
+    #[test]
+    fn test_get_every_value() {
+        let data: Vec<u32> = (0..1500u32)
+            .map(|i| i.wrapping_mul(2_654_435_761) >> (i % 29))
+            .collect();
+        let mut codec = small_pages();
+        let encoded = encode(&mut codec, &data);
+        assert_eq!(SeekableFastPFor128::value_count(&encoded).unwrap(), 1500);
+        for (i, &value) in data.iter().enumerate() {
+            assert_eq!(codec.get(&encoded, i).unwrap(), value, "index {i}");
+        }
+    }
+
+    #[test]
+    fn test_decode_ranges() {
+        let data: Vec<u32> = (0..1500u32)
+            .map(|i| if i % 37 == 0 { 1 << (i % 32) } else { i % 50 })
+            .collect();
+        let mut codec = small_pages();
+        let encoded = encode(&mut codec, &data);
+        for range in [
+            0..0,
+            0..1500,
+            3..5,
+            100..700,
+            250..260,
+            255..257,
+            1400..1500,
+            1499..1500,
+            1500..1500,
+        ] {
+            let mut out = vec![7];
+            codec
+                .decode_range(&encoded, range.clone(), &mut out)
+                .unwrap();
+            assert_eq!(out[0], 7);
+            assert_eq!(out[1..], data[range.clone()], "range {range:?}");
+        }
+    }
+
+    #[test]
+    fn test_checksummed_pages() {
+        let config = FastPForConfig::new(256).unwrap().with_checksums(true);
+        let mut codec = SeekableFastPFor128::from_config(config);
+        let data: Vec<u32> = (0..1000).map(|i| i * 3).collect();
+        let mut encoded = encode(&mut codec, &data);
+        let mut out = Vec::new();
+        codec.decode_range(&encoded, 500..800, &mut out).unwrap();
+        assert_eq!(out, data[500..800]);
+
+        // Corrupt page 1; pages 0 and 2 stay readable.
+        let page_1 = HEADER_WORDS + encoded[1] as usize + 1 + encoded[HEADER_WORDS + 1] as usize;
+        encoded[page_1 + 4] ^= 1;
+        assert_eq!(codec.get(&encoded, 10).unwrap(), 30);
+        assert_eq!(codec.get(&encoded, 600).unwrap(), 1800);
+        assert!(matches!(
+            codec.get(&encoded, 300),
+            Err(FastPForError::ChecksumMismatch { page: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_out_of_bounds_errors() {
+        let mut codec = SeekableFastPFor256::default();
+        let encoded = encode(&mut codec, &[1, 2, 3]);
+        assert!(matches!(
+            codec.get(&encoded, 3),
+            Err(FastPForError::RangeOutOfBounds {
+                start: 3,
+                end: 4,
+                len: 3
+            })
+        ));
+        // Far out of bounds: must not try to reserve the range up front.
+        assert!(matches!(
+            codec.decode_range(&encoded, 0..usize::MAX, &mut Vec::new()),
+            Err(FastPForError::RangeOutOfBounds {
+                start: 0,
+                end: usize::MAX,
+                len: 3
+            })
+        ));
+        #[expect(clippy::reversed_empty_ranges)]
+        let reversed = 2..1;
+        assert!(
+            codec
+                .decode_range(&encoded, reversed, &mut Vec::new())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_mismatched_page_size_errors() {
+        let data: Vec<u32> = (0..1024).collect();
+        let encoded = encode(&mut small_pages(), &data);
+        let mut codec = SeekableFastPFor128::default();
+        assert!(matches!(
+            codec.get(&encoded, 0),
+            Err(FastPForError::NotEnoughData)
+        ));
+    }
+
+    #[test]
+    fn test_truncated_index_errors() {
+        let data: Vec<u32> = (0..1024).collect();
+        let mut codec = small_pages();
+        let encoded = encode(&mut codec, &data);
+        for len in [1, 3, 6] {
+            assert!(codec.get(&encoded[..len], 0).is_err());
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! [`StreamInfo`]: what validating an encoded stream found.
+
+/// Summary of an encoded stream, returned by the `validate` methods of
+/// [`FastPFor`](crate::FastPFor), [`VariableByte`](crate::VariableByte) and the
+/// `FastPFor` composites.
+///
+/// `validate` walks a stream exactly as decoding would, checking headers,
+/// metadata bytes and exception bitmaps, but without unpacking any values or
+/// allocating output. A stream that validates decodes without error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamInfo {
+    value_count: usize,
+    page_count: usize,
+    consumed_words: usize,
+}
+
+impl StreamInfo {
+    pub(crate) const fn new(value_count: usize, page_count: usize, consumed_words: usize) -> Self {
+        Self {
+            value_count,
+            page_count,
+            consumed_words,
+        }
+    }
+
+    /// Number of values the stream decodes to.
+    #[must_use]
+    pub const fn value_count(&self) -> usize {
+        self.value_count
+    }
+
+    /// Number of `FastPFor` pages; `0` for `VariableByte`.
+    #[must_use]
+    pub const fn page_count(&self) -> usize {
+        self.page_count
+    }
+
+    /// Number of `u32` words of input that make up the stream.
+    #[must_use]
+    pub const fn consumed_words(&self) -> usize {
+        self.consumed_words
+    }
+}
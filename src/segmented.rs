@@ -0,0 +1,251 @@
+//! [`SegmentedCodec`]: a container of independently encoded segments, so that
+//! encoded streams can be merged without decoding them.
+
+use alloc::vec::Vec;
+
+use crate::codec::AnyLenCodec;
+use crate::helpers::AsUsize;
+use crate::segments::{next_segment, split_header};
+use crate::{Element, FastPForError, FastPForResult};
+
+/// Wraps any [`AnyLenCodec`] in a container of independently encoded segments.
+///
+/// A [`CompositeCodec`](crate::CompositeCodec) stream ends in a tail that
+/// cannot be spliced into another stream, so merging two of them would mean
+/// decoding and re-encoding. A `SegmentedCodec` stream instead lists whole
+/// inner streams: [`concat_encoded`](Self::concat_encoded) merges containers
+/// and [`from_segments`](Self::from_segments) wraps existing inner streams by
+/// copying words and rewriting headers only. Decoding yields the values of
+/// every segment in order.
+///
+/// [`encode`](AnyLenCodec::encode) writes a single segment.
+///
+/// # Wire format
+///
+/// ```text
+/// [ segment_count: u32 ]
+/// [ words: u32 ] [ segment 0 encoded data... ]
+/// [ words: u32 ] [ segment 1 encoded data... ]
+/// ...
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use fastpfor::{AnyLenCodec, FastPFor256, SegmentedCodec};
+///
+/// let mut codec = SegmentedCodec::<FastPFor256>::default();
+/// let (mut a, mut b) = (Vec::new(), Vec::new());
+/// codec.encode(&[1, 2, 3], &mut a).unwrap();
+/// codec.encode(&[4, 5], &mut b).unwrap();
+///
+/// let merged = SegmentedCodec::<FastPFor256>::concat_encoded(&[&a, &b]).unwrap();
+/// let mut decoded = Vec::new();
+/// codec.decode(&merged, &mut decoded, None).unwrap();
+/// assert_eq!(decoded, [1, 2, 3, 4, 5]);
+/// ```
+#[derive(Debug, Default)]
+pub struct SegmentedCodec<C> {
+    inner: C,
+}
+
+impl<C> SegmentedCodec<C> {
+    /// Creates a `SegmentedCodec` that encodes and decodes segments with `inner`.
+    pub fn new(inner: C) -> Self {
+        Self { inner }
+    }
+
+    /// Builds a container whose segments are the given streams of the inner
+    /// codec, without decoding them.
+    pub fn from_segments(segments: &[&[u32]]) -> FastPForResult<Vec<u32>> {
+        let mut out = Vec::with_capacity(1 + segments.iter().map(|s| 1 + s.len()).sum::<usize>());
+        out.push(segment_count(segments.len())?);
+        for segment in segments {
+            out.push(
+                u32::try_from(segment.len())
+                    .map_err(|_| FastPForError::InvalidInputLength(segment.len()))?,
+            );
+            out.extend_from_slice(segment);
+        }
+        Ok(out)
+    }
+
+    /// Concatenates containers into one holding all their segments in order,
+    /// without decoding them.
+    ///
+    /// Only the segment lengths are read; the segments themselves are copied
+    /// unchecked.
+    pub fn concat_encoded(inputs: &[&[u32]]) -> FastPForResult<Vec<u32>> {
+        let mut out = Vec::from([0]);
+        let mut count: usize = 0;
+        for input in inputs {
+            let (n, mut rest) = read_header(input)?;
+            for _ in 0..n {
+                next_segment(&mut rest)?;
+            }
+            if !rest.is_empty() {
+                return Err(FastPForError::InvalidInputLength(input.len()));
+            }
+            out.extend_from_slice(&input[1..]);
+            count += n.as_usize();
+        }
+        out[0] = segment_count(count)?;
+        Ok(out)
+    }
+}
+
+fn segment_count(count: usize) -> FastPForResult<u32> {
+    u32::try_from(count).map_err(|_| FastPForError::InvalidInputLength(count))
+}
+
+/// Splits a stream into its segment count and segment entries.
+fn read_header(input: &[u32]) -> FastPForResult<(u32, &[u32])> {
+    let ([count], rest) = split_header::<1>(input)?;
+    Ok((*count, rest))
+}
+
+impl<T: Element, C: AnyLenCodec<T>> AnyLenCodec<T> for SegmentedCodec<C> {
+    fn encode(&mut self, input: &[T], out: &mut Vec<u32>) -> FastPForResult<()> {
+        let start = out.len();
+        out.extend([1, 0]);
+        self.inner.encode(input, out)?;
+        let words = out.len() - start - 2;
+        out[start + 1] =
+            u32::try_from(words).map_err(|_| FastPForError::InvalidInputLength(words))?;
+        Ok(())
+    }
+
+    fn max_compressed_len(&self, n_values: usize) -> usize {
+        2 + self.inner.max_compressed_len(n_values)
+    }
+
+    fn encode_into(&mut self, input: &[T], out: &mut [u32]) -> FastPForResult<usize> {
+        let Some(([count, words], rest)) = out.split_first_chunk_mut::<2>() else {
+            return Err(FastPForError::OutputBufferTooSmall);
+        };
+        let n = self.inner.encode_into(input, rest)?;
+        *count = 1;
+        *words = u32::try_from(n).map_err(|_| FastPForError::InvalidInputLength(n))?;
+        Ok(2 + n)
+    }
+
+    fn decode(
+        &mut self,
+        input: &[u32],
+        out: &mut Vec<T>,
+        expected_len: Option<u32>,
+    ) -> FastPForResult<()> {
+        let start_len = out.len();
+        if let Some(expected) = expected_len {
+            out.reserve(expected.is_valid_expected(Self::max_decompressed_len(input.len()))?);
+        }
+        let (count, mut rest) = read_header(input)?;
+        for _ in 0..count {
+            let segment = next_segment(&mut rest)?;
+            self.inner.decode(segment, out, None)?;
+        }
+        if !rest.is_empty() {
+            return Err(FastPForError::InvalidInputLength(input.len()));
+        }
+        if let Some(n) = expected_len {
+            (out.len() - start_len).is_decoded_mismatch(n)?;
+        }
+        Ok(())
+    }
+
+    fn decode_into(&mut self, input: &[u32], out: &mut [T]) -> FastPForResult<usize> {
+        let (count, mut rest) = read_header(input)?;
+        let mut written = 0;
+        for _ in 0..count {
+            let segment = next_segment(&mut rest)?;
+            written += self.inner.decode_into(segment, &mut out[written..])?;
+        }
+        if !rest.is_empty() {
+            return Err(FastPForError::InvalidInputLength(input.len()));
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(all(test, feature = "rust"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{compress, decompress, roundtrip, roundtrip_into};
+    use crate::{FastPFor128, FastPFor256};
+
+    type Segmented = SegmentedCodec<FastPFor256>;
+
+    #[test]
+    fn test_roundtrip() {
+        let data: Vec<u32> = (0..1000u32).map(|i| i * 7 % 513).collect();
+        roundtrip::<Segmented>(&data);
+        roundtrip::<Segmented>(&[]);
+        roundtrip_into::<u32, SegmentedCodec<FastPFor128>>(&data);
+    }
+
+    #[test]
+    fn test_concat_containers() {
+        let data: Vec<u32> = (0..3000u32)
+            .map(|i| i.wrapping_mul(2_654_435_761))
+            .collect();
+        let parts: Vec<Vec<u32>> = data
+            .chunks(700)
+            .map(|chunk| compress::<Segmented>(chunk).unwrap())
+            .collect();
+        let refs: Vec<&[u32]> = parts.iter().map(Vec::as_slice).collect();
+
+        let merged = Segmented::concat_encoded(&refs).unwrap();
+        assert_eq!(merged[0], 5);
+        assert_eq!(decompress::<Segmented>(&merged, Some(3000)).unwrap(), data);
+
+        // Containers of several segments merge as well.
+        let twice = Segmented::concat_encoded(&[&merged, &merged]).unwrap();
+        assert_eq!(twice[0], 10);
+        let decoded = decompress::<Segmented>(&twice, None).unwrap();
+        assert_eq!(decoded[..3000], data);
+        assert_eq!(decoded[3000..], data);
+
+        let mut out = vec![0; 6000];
+        assert_eq!(
+            Segmented::default().decode_into(&twice, &mut out).unwrap(),
+            6000
+        );
+        assert_eq!(out[3000..], data);
+    }
+
+    #[test]
+    fn test_from_composite_streams() {
+        let a = compress::<FastPFor256>(&[1, 2, 3]).unwrap();
+        let b = compress::<FastPFor256>(&(0..600).collect::<Vec<_>>()).unwrap();
+        let merged = Segmented::from_segments(&[&a, &b]).unwrap();
+        let decoded = decompress::<Segmented>(&merged, Some(603)).unwrap();
+        assert_eq!(decoded[..3], [1, 2, 3]);
+        assert_eq!(decoded[3..], (0..600).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_concat_nothing_is_empty() {
+        let merged = Segmented::concat_encoded(&[]).unwrap();
+        assert_eq!(merged, [0]);
+        assert!(decompress::<Segmented>(&merged, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_truncated_or_trailing_errors() {
+        let encoded = compress::<Segmented>(&[1, 2, 3]).unwrap();
+        let truncated = &encoded[..encoded.len() - 1];
+        assert!(Segmented::concat_encoded(&[truncated]).is_err());
+        assert!(decompress::<Segmented>(truncated, None).is_err());
+
+        let mut trailing = encoded.clone();
+        trailing.push(0);
+        assert!(matches!(
+            Segmented::concat_encoded(&[&trailing]),
+            Err(FastPForError::InvalidInputLength(_))
+        ));
+        assert!(matches!(
+            decompress::<Segmented>(&trailing, None),
+            Err(FastPForError::InvalidInputLength(_))
+        ));
+    }
+}
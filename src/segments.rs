@@ -0,0 +1,60 @@
+//! Reading the segment lists shared by [`ChunkedCodec`](crate::ChunkedCodec)
+//! and [`SegmentedCodec`](crate::SegmentedCodec): a fixed header followed by
+//! `[words][segment data]` entries.
+
+use crate::helpers::AsUsize;
+use crate::{FastPForError, FastPForResult};
+
+/// Splits the `HEADER`-word header off the front of a stream.
+pub(crate) fn split_header<const HEADER: usize>(
+    input: &[u32],
+) -> FastPForResult<(&[u32; HEADER], &[u32])> {
+    input
+        .split_first_chunk::<HEADER>()
+        .ok_or(FastPForError::NotEnoughData)
+}
+
+/// Splits the next `[words][segment data]` entry off the front of `rest`.
+pub(crate) fn next_segment<'a>(rest: &mut &'a [u32]) -> FastPForResult<&'a [u32]> {
+    let Some((&words, tail)) = rest.split_first() else {
+        return Err(FastPForError::NotEnoughData);
+    };
+    let Some((segment, tail)) = tail.split_at_checked(words.as_usize()) else {
+        return Err(FastPForError::NotEnoughData);
+    };
+    *rest = tail;
+    Ok(segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_segment_walks_entries() {
+        let mut rest: &[u32] = &[2, 7, 8, 0, 1, 9];
+        assert_eq!(next_segment(&mut rest).unwrap(), [7, 8]);
+        assert!(next_segment(&mut rest).unwrap().is_empty());
+        assert_eq!(next_segment(&mut rest).unwrap(), [9]);
+        assert!(rest.is_empty());
+        assert!(matches!(
+            next_segment(&mut rest),
+            Err(FastPForError::NotEnoughData)
+        ));
+    }
+
+    #[test]
+    fn test_truncated_input_is_not_enough_data() {
+        let mut rest: &[u32] = &[3, 1, 2];
+        assert!(matches!(
+            next_segment(&mut rest),
+            Err(FastPForError::NotEnoughData)
+        ));
+        assert!(matches!(
+            split_header::<3>(&[1, 2]),
+            Err(FastPForError::NotEnoughData)
+        ));
+        let (header, rest) = split_header::<1>(&[4, 5]).unwrap();
+        assert_eq!((header, rest), (&[4], &[5][..]));
+    }
+}
@@ -0,0 +1,559 @@
+//! [`StreamEncoder`] and [`StreamDecoder`]: encode values as they arrive into
+//! any [`Write`], one length-prefixed frame per page, and decode them back from
+//! any [`Read`] one frame at a time.
+
+use std::io::{ErrorKind, Read, Write};
+use std::num::NonZeroU32;
+
+use crate::codec::{AnyLenCodec, extend_le_bytes, with_le_words};
+use crate::helpers::AsUsize;
+use crate::{FastPForError, FastPForResult};
+
+/// Number of bytes in a frame header: value count and word count.
+const FRAME_HEADER_BYTES: usize = 8;
+
+/// Number of values per frame for codecs without pages: one full `FastPFor`
+/// page with the default page size.
+const DEFAULT_FRAME_LEN: u32 = 65536;
+
+/// Encodes a stream of `u32` values into a [`Write`] without holding all of
+/// them in memory.
+///
+/// Values are buffered until a frame of `frame_len` values is complete (by
+/// default the codec's [`page_len`](AnyLenCodec::page_len), or 65536 for
+/// codecs without pages), and each frame is encoded on its own with the codec and written out. The last,
+/// partial frame, and with it a composite's `VariableByte` tail, is written by
+/// [`finish`](Self::finish), which must be called: dropping the encoder
+/// discards buffered values.
+///
+/// # Wire format
+///
+/// ```text
+/// [ values: u32 ] [ words: u32 ] [ frame 0 encoded data... ]
+/// [ values: u32 ] [ words: u32 ] [ frame 1 encoded data... ]
+/// ...
+/// ```
+///
+/// All words are little-endian. The stream ends with the last frame; every
+/// frame but the last holds exactly `frame_len` values.
+///
+/// # Example
+///
+/// ```
+/// use fastpfor::{FastPFor256, StreamEncoder};
+///
+/// let mut encoder = StreamEncoder::new(FastPFor256::default(), Vec::new());
+/// for chunk in (0..200_000u32).collect::<Vec<_>>().chunks(1000) {
+///     encoder.write_values(chunk).unwrap();
+/// }
+/// let bytes: Vec<u8> = encoder.finish().unwrap();
+/// assert!(bytes.len() < 200_000 * 4);
+/// ```
+#[derive(Debug)]
+pub struct StreamEncoder<C, W: Write> {
+    codec: C,
+    writer: W,
+    frame_len: usize,
+    /// Values of the current, incomplete frame
+    values: Vec<u32>,
+    encoded: Vec<u32>,
+    bytes: Vec<u8>,
+}
+
+/// Default frame length for `codec`: its page length, or
+/// [`DEFAULT_FRAME_LEN`] if it has no pages.
+fn default_frame_len(codec: &impl AnyLenCodec<u32>) -> NonZeroU32 {
+    codec
+        .page_len()
+        .and_then(|len| u32::try_from(len).ok())
+        .and_then(NonZeroU32::new)
+        .or(NonZeroU32::new(DEFAULT_FRAME_LEN))
+        .expect("DEFAULT_FRAME_LEN is not zero")
+}
+
+impl<C: AnyLenCodec<u32>, W: Write> StreamEncoder<C, W> {
+    /// Creates an encoder that writes frames of one codec page to `writer`.
+    ///
+    /// The frame length is the codec's [`page_len`](AnyLenCodec::page_len),
+    /// so that every frame but the last is made of full pages, or 65536
+    /// values if the codec has no pages.
+    pub fn new(codec: C, writer: W) -> Self {
+        let frame_len = default_frame_len(&codec);
+        Self::with_frame_len(codec, writer, frame_len)
+    }
+
+    /// Creates an encoder that writes frames of `frame_len` values to `writer`.
+    ///
+    /// Prefer a multiple of the codec's page size, or at least of its block
+    /// size, so that only the last frame needs a tail encoding.
+    pub fn with_frame_len(codec: C, writer: W, frame_len: NonZeroU32) -> Self {
+        Self {
+            codec,
+            writer,
+            frame_len: frame_len.get() as usize,
+            values: Vec::new(),
+            encoded: Vec::new(),
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Appends `values` to the stream, writing out every frame they complete.
+    pub fn write_values(&mut self, mut values: &[u32]) -> FastPForResult<()> {
+        while !values.is_empty() {
+            if self.values.is_empty() && values.len() >= self.frame_len {
+                // Whole frames are encoded straight from the caller's slice.
+                let (frame, rest) = values.split_at(self.frame_len);
+                self.write_frame(frame)?;
+                values = rest;
+                continue;
+            }
+            let n = (self.frame_len - self.values.len()).min(values.len());
+            self.values.extend_from_slice(&values[..n]);
+            values = &values[n..];
+            if self.values.len() == self.frame_len {
+                self.flush_values()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the last, partial frame, flushes the writer and returns it.
+    pub fn finish(mut self) -> FastPForResult<W> {
+        if !self.values.is_empty() {
+            self.flush_values()?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Writes the buffered values as one frame.
+    fn flush_values(&mut self) -> FastPForResult<()> {
+        let values = core::mem::take(&mut self.values);
+        let result = self.write_frame(&values);
+        self.values = values;
+        self.values.clear();
+        result
+    }
+
+    fn write_frame(&mut self, values: &[u32]) -> FastPForResult<()> {
+        self.encoded.clear();
+        self.codec.encode(values, &mut self.encoded)?;
+        let words = self.encoded.len();
+        let words = u32::try_from(words).map_err(|_| FastPForError::InvalidInputLength(words))?;
+        self.bytes.clear();
+        // `values.len() <= frame_len`, which is a `u32`.
+        extend_le_bytes(&[values.len() as u32, words], &mut self.bytes);
+        extend_le_bytes(&self.encoded, &mut self.bytes);
+        self.writer.write_all(&self.bytes)?;
+        Ok(())
+    }
+}
+
+/// Decodes a stream written by [`StreamEncoder`] from a [`Read`], one frame at
+/// a time.
+///
+/// Only the current frame is held in memory, both compressed and decoded.
+/// Before a frame is read, its header is checked: a frame of more than
+/// `max_frame_len` values fails with
+/// [`ExpectedCountExceedsMax`](FastPForError::ExpectedCountExceedsMax), and
+/// one of more words than
+/// [`max_compressed_len`](AnyLenCodec::max_compressed_len) of its value count
+/// with [`FrameTooLarge`](FastPForError::FrameTooLarge). A corrupt header
+/// therefore cannot make the decoder buffer more than one frame's worth of
+/// data. The value count is also passed to the codec as `expected_len`, so it
+/// is checked against
+/// [`max_decompressed_len`](AnyLenCodec::max_decompressed_len) of the frame's
+/// length before any output is allocated.
+///
+/// Values can be pulled into a caller's buffer with
+/// [`read_values`](Self::read_values), borrowed a frame at a time with
+/// [`next_chunk`](Self::next_chunk), or collected through the [`Iterator`]
+/// implementation, which yields one `Vec` per frame.
+///
+/// # Example
+///
+/// ```
+/// use fastpfor::{FastPFor256, StreamDecoder, StreamEncoder};
+///
+/// let data: Vec<u32> = (0..200_000).map(|i| i % 1000).collect();
+/// let mut encoder = StreamEncoder::new(FastPFor256::default(), Vec::new());
+/// encoder.write_values(&data).unwrap();
+/// let bytes = encoder.finish().unwrap();
+///
+/// let mut decoder = StreamDecoder::new(FastPFor256::default(), bytes.as_slice());
+/// let mut buf = [0; 4096];
+/// let mut sum = 0u64;
+/// loop {
+///     let n = decoder.read_values(&mut buf).unwrap();
+///     if n == 0 {
+///         break;
+///     }
+///     sum += buf[..n].iter().map(|&v| u64::from(v)).sum::<u64>();
+/// }
+/// assert_eq!(sum, data.iter().map(|&v| u64::from(v)).sum::<u64>());
+/// ```
+#[derive(Debug)]
+pub struct StreamDecoder<C, R: Read> {
+    codec: C,
+    reader: R,
+    max_frame_len: usize,
+    bytes: Vec<u8>,
+    /// Decoded values of the current frame
+    values: Vec<u32>,
+    /// Number of values of the current frame already handed out
+    pos: usize,
+    /// Set once the stream has ended or failed
+    done: bool,
+}
+
+impl<C: AnyLenCodec<u32>, R: Read> StreamDecoder<C, R> {
+    /// Creates a decoder that reads frames from `reader`, accepting frames of
+    /// up to the [`StreamEncoder`] default frame length for `codec`.
+    pub fn new(codec: C, reader: R) -> Self {
+        let max_frame_len = default_frame_len(&codec);
+        Self::with_max_frame_len(codec, reader, max_frame_len)
+    }
+
+    /// Creates a decoder that reads frames of up to `max_frame_len` values
+    /// from `reader`, e.g. a stream written with
+    /// [`StreamEncoder::with_frame_len`].
+    pub fn with_max_frame_len(codec: C, reader: R, max_frame_len: NonZeroU32) -> Self {
+        Self {
+            codec,
+            reader,
+            max_frame_len: max_frame_len.get() as usize,
+            bytes: Vec::new(),
+            values: Vec::new(),
+            pos: 0,
+            done: false,
+        }
+    }
+
+    /// Decodes values into `out`, returning how many were written.
+    ///
+    /// Returns `0` only at the end of the stream (or if `out` is empty).
+    pub fn read_values(&mut self, out: &mut [u32]) -> FastPForResult<usize> {
+        let mut written = 0;
+        while written < out.len() {
+            let Some(chunk) = self.next_chunk_up_to(out.len() - written)? else {
+                break;
+            };
+            out[written..written + chunk.len()].copy_from_slice(chunk);
+            written += chunk.len();
+        }
+        Ok(written)
+    }
+
+    /// Returns the values of the current frame not read yet, decoding the
+    /// next frame if there are none, or `None` at the end of the stream.
+    pub fn next_chunk(&mut self) -> FastPForResult<Option<&[u32]>> {
+        self.next_chunk_up_to(usize::MAX)
+    }
+
+    /// Like [`next_chunk`](Self::next_chunk), handing out at most `max` values.
+    fn next_chunk_up_to(&mut self, max: usize) -> FastPForResult<Option<&[u32]>> {
+        while self.pos == self.values.len() {
+            if self.done {
+                return Ok(None);
+            }
+            if let Err(err) = self.read_frame() {
+                self.done = true;
+                return Err(err);
+            }
+        }
+        let start = self.pos;
+        self.pos += max.min(self.values.len() - start);
+        Ok(Some(&self.values[start..self.pos]))
+    }
+
+    /// Reads and decodes the next frame into `values`, or sets `done` at the
+    /// end of the stream.
+    fn read_frame(&mut self) -> FastPForResult<()> {
+        self.values.clear();
+        self.pos = 0;
+        let mut header = [0; FRAME_HEADER_BYTES];
+        if !read_header(&mut self.reader, &mut header)? {
+            self.done = true;
+            return Ok(());
+        }
+        let count = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        let words = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        if count.as_usize() > self.max_frame_len {
+            return Err(FastPForError::ExpectedCountExceedsMax {
+                expected: count.as_usize(),
+                max: self.max_frame_len,
+            });
+        }
+        let max_words = self.codec.max_compressed_len(count.as_usize());
+        if words.as_usize() > max_words {
+            return Err(FastPForError::FrameTooLarge {
+                values: count.as_usize(),
+                words: words.as_usize(),
+                max: max_words,
+            });
+        }
+
+        // Read through `take` so that a truncated stream fails once the
+        // reader runs out, without allocating the whole frame up front.
+        let len = u64::from(words) * 4;
+        self.bytes.clear();
+        (&mut self.reader).take(len).read_to_end(&mut self.bytes)?;
+        if self.bytes.len() as u64 != len {
+            return Err(FastPForError::NotEnoughData);
+        }
+        with_le_words(&self.bytes, |words| {
+            self.codec.decode(words, &mut self.values, Some(count))
+        })
+    }
+}
+
+/// Fills `header` from `reader`. Returns `false` if the reader is already at
+/// its end, and an error if it ends partway through the header.
+fn read_header(reader: &mut impl Read, header: &mut [u8]) -> FastPForResult<bool> {
+    let mut filled = 0;
+    while filled < header.len() {
+        match reader.read(&mut header[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(FastPForError::NotEnoughData),
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(true)
+}
+
+impl<C: AnyLenCodec<u32>, R: Read> Iterator for StreamDecoder<C, R> {
+    type Item = FastPForResult<Vec<u32>>;
+
+    /// Yields the values of each frame not read yet, then stops after the end
+    /// of the stream or the first error.
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_chunk() {
+            Ok(Some(chunk)) => Some(Ok(chunk.to_vec())),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rust"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use crate::test_utils::decompress;
+    use crate::{CompositeCodec, FastPFor, FastPFor128, FastPFor256, VariableByte};
+
+    /// Splits a stream into its frames' value counts and decoded values.
+    fn read_frames(bytes: &[u8]) -> (Vec<u32>, Vec<u32>) {
+        let words: Vec<u32> = bytes
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        let (mut counts, mut values) = (Vec::new(), Vec::new());
+        let mut rest = &words[..];
+        while let [count, len, tail @ ..] = rest {
+            let (frame, tail) = tail.split_at(*len as usize);
+            values.extend(decompress::<FastPFor256>(frame, Some(*count)).unwrap());
+            counts.push(*count);
+            rest = tail;
+        }
+        assert!(rest.is_empty());
+        (counts, values)
+    }
+
+    fn data(len: u32) -> Vec<u32> {
+        (0..len)
+            .map(|i| i.wrapping_mul(2_654_435_761) >> (i % 29))
+            .collect()
+    }
+
+    #[test]
+    fn test_frames_are_full_pages() {
+        let data = data(150_000);
+        for chunk_len in [1, 999, 65_536, 150_000] {
+            let mut encoder = StreamEncoder::new(FastPFor256::default(), Vec::new());
+            for chunk in data.chunks(chunk_len) {
+                encoder.write_values(chunk).unwrap();
+            }
+            let (counts, values) = read_frames(&encoder.finish().unwrap());
+            assert_eq!(counts, [65_536, 65_536, 18_928]);
+            assert_eq!(values, data);
+        }
+    }
+
+    #[test]
+    fn test_frames_follow_codec_page_size() {
+        let data = data(3000);
+        let codec = CompositeCodec::new(FastPFor::<256>::new(1024).unwrap(), VariableByte);
+        let mut encoder = StreamEncoder::new(codec, Vec::new());
+        encoder.write_values(&data).unwrap();
+        let (counts, values) = read_frames(&encoder.finish().unwrap());
+        assert_eq!(counts, [1024, 1024, 952]);
+        assert_eq!(values, data);
+    }
+
+    #[test]
+    fn test_codec_without_pages_uses_default_frame_len() {
+        let mut encoder = StreamEncoder::new(VariableByte, Vec::new());
+        encoder.write_values(&data(70_000)).unwrap();
+        let bytes = encoder.finish().unwrap();
+        let count = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+        assert_eq!(count, DEFAULT_FRAME_LEN);
+    }
+
+    #[test]
+    fn test_custom_frame_len() {
+        let data = data(1000);
+        let frame_len = NonZeroU32::new(256).unwrap();
+        let mut encoder =
+            StreamEncoder::with_frame_len(FastPFor256::default(), Vec::new(), frame_len);
+        encoder.write_values(&data).unwrap();
+        let (counts, values) = read_frames(&encoder.finish().unwrap());
+        assert_eq!(counts, [256, 256, 256, 232]);
+        assert_eq!(values, data);
+    }
+
+    #[test]
+    fn test_empty_stream_has_no_frames() {
+        let encoder = StreamEncoder::new(FastPFor128::default(), Vec::new());
+        assert!(encoder.finish().unwrap().is_empty());
+    }
+
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_writer_errors_are_returned() {
+        let mut encoder = StreamEncoder::new(FastPFor256::default(), FailingWriter);
+        encoder.write_values(&[1, 2, 3]).unwrap();
+        assert!(matches!(encoder.finish(), Err(FastPForError::Io(_))));
+    }
+
+    fn encode(data: &[u32], frame_len: u32) -> Vec<u8> {
+        let frame_len = NonZeroU32::new(frame_len).unwrap();
+        let mut encoder =
+            StreamEncoder::with_frame_len(FastPFor128::default(), Vec::new(), frame_len);
+        encoder.write_values(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_read_values_across_frames() {
+        let data = data(10_000);
+        let bytes = encode(&data, 1024);
+        for buf_len in [1, 100, 1024, 5000, 20_000] {
+            let mut reader = StreamDecoder::new(FastPFor128::default(), bytes.as_slice());
+            let mut buf = vec![0; buf_len];
+            let mut decoded = Vec::new();
+            loop {
+                let n = reader.read_values(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                decoded.extend_from_slice(&buf[..n]);
+            }
+            assert_eq!(decoded, data);
+            assert_eq!(reader.read_values(&mut buf).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn test_iterator_yields_frames() {
+        let data = data(150_000);
+        let mut encoder = StreamEncoder::new(FastPFor256::default(), Vec::new());
+        encoder.write_values(&data).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        let decoder = StreamDecoder::new(FastPFor256::default(), io::Cursor::new(bytes));
+        let frames: Vec<Vec<u32>> = decoder.collect::<FastPForResult<_>>().unwrap();
+        assert_eq!(
+            frames.iter().map(Vec::len).collect::<Vec<_>>(),
+            [65_536, 65_536, 18_928]
+        );
+        assert_eq!(frames.concat(), data);
+    }
+
+    #[test]
+    fn test_empty_stream() {
+        let mut decoder = StreamDecoder::new(FastPFor128::default(), &[][..]);
+        assert!(decoder.next_chunk().unwrap().is_none());
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn test_truncated_stream_errors_once() {
+        let bytes = encode(&data(3000), 1024);
+        for len in [3, FRAME_HEADER_BYTES + 4, bytes.len() - 4] {
+            let mut decoder = StreamDecoder::new(FastPFor128::default(), &bytes[..len]);
+            let results: Vec<_> = decoder.by_ref().collect();
+            assert!(matches!(
+                results.last(),
+                Some(Err(FastPForError::NotEnoughData))
+            ));
+            assert!(decoder.next().is_none());
+        }
+    }
+
+    #[test]
+    fn test_frame_count_is_checked_before_allocating() {
+        let mut bytes = encode(&[1, 2, 3], 1024);
+        // Claim far more values than 2 words can hold.
+        bytes[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        let mut decoder = StreamDecoder::new(FastPFor128::default(), bytes.as_slice());
+        assert!(matches!(
+            decoder.next_chunk(),
+            Err(FastPForError::ExpectedCountExceedsMax { .. })
+        ));
+
+        // More values than the decoder accepts in a frame.
+        let bytes = encode(&data(3000), 3000);
+        let max_frame_len = NonZeroU32::new(1024).unwrap();
+        let mut decoder = StreamDecoder::with_max_frame_len(
+            FastPFor128::default(),
+            bytes.as_slice(),
+            max_frame_len,
+        );
+        assert!(matches!(
+            decoder.next_chunk(),
+            Err(FastPForError::ExpectedCountExceedsMax {
+                expected: 3000,
+                max: 1024
+            })
+        ));
+        let decoder = StreamDecoder::new(FastPFor128::default(), bytes.as_slice());
+        let frames: Vec<Vec<u32>> = decoder.collect::<FastPForResult<_>>().unwrap();
+        assert_eq!(frames.concat(), data(3000));
+    }
+
+    #[test]
+    fn test_huge_word_count_is_rejected_before_reading() {
+        // A corrupt header in front of a long payload.
+        let mut bytes = Vec::from(3u32.to_le_bytes());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.resize(FRAME_HEADER_BYTES + (1 << 20), 0);
+        let mut reader = io::Cursor::new(bytes);
+        let mut decoder = StreamDecoder::new(FastPFor128::default(), &mut reader);
+        assert!(matches!(
+            decoder.next_chunk(),
+            Err(FastPForError::FrameTooLarge {
+                values: 3,
+                words,
+                ..
+            }) if words == u32::MAX as usize
+        ));
+        assert!(decoder.next().is_none());
+        drop(decoder);
+        assert_eq!(reader.position(), FRAME_HEADER_BYTES as u64);
+    }
+}
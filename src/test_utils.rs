@@ -10,10 +10,10 @@
 // noise without benefit.
 #![allow(dead_code, missing_docs)]
 
-#[cfg(feature = "cpp")]
-use fastpfor::BlockCodec64;
 #[allow(unused_imports)]
-use fastpfor::{AnyLenCodec, BlockCodec, FastPForResult, slice_to_blocks};
+use fastpfor::{
+    AnyLenCodec, BlockCodec, Element, FastPForError, FastPForResult, slice_to_blocks_of,
+};
 #[cfg(feature = "rust")]
 use fastpfor::{
     FastPFor128, FastPFor256, FastPForBlock128, FastPForBlock256, JustCopy, VariableByte,
@@ -48,14 +48,49 @@ pub fn roundtrip_full<E: AnyLenCodec, D: AnyLenCodec>(data: &[u32], expected_len
     assert_eq!(decompressed, data);
 }
 
-#[cfg(feature = "cpp")]
-pub fn roundtrip64<C: BlockCodec64 + Default>(data: &[u64]) {
-    let mut codec = C::default();
+/// Like [`roundtrip`], for codecs over a non-default [`Element`] type.
+pub fn roundtrip_elements<T: Element, C: AnyLenCodec<T>>(data: &[T]) {
+    let mut compressed = Vec::new();
+    C::default().encode(data, &mut compressed).unwrap();
+    let mut decompressed = Vec::new();
+    C::default()
+        .decode(
+            &compressed,
+            &mut decompressed,
+            Some(data.len().try_into().unwrap()),
+        )
+        .unwrap();
+    assert_eq!(decompressed, data);
+}
+
+/// Encode `data`, decode it with [`AnyLenCodec::decode_into`] into a slice with spare
+/// room, assert round-trip, and assert that a slice one value short is rejected.
+pub fn roundtrip_into<T: Element, C: AnyLenCodec<T>>(data: &[T]) {
     let mut compressed = Vec::new();
-    codec.encode64(data, &mut compressed).unwrap();
-    let mut decoded = Vec::new();
-    codec.decode64(&compressed, &mut decoded).unwrap();
-    assert_eq!(decoded, data);
+    C::default().encode(data, &mut compressed).unwrap();
+    let mut out = vec![T::default(); data.len() + 8];
+    let written = C::default().decode_into(&compressed, &mut out).unwrap();
+    assert_eq!(&out[..written], data);
+    if let Some(short) = data.len().checked_sub(1) {
+        let err = C::default()
+            .decode_into(&compressed, &mut out[..short])
+            .unwrap_err();
+        assert!(
+            matches!(err, FastPForError::OutputBufferTooSmall),
+            "{err:?}"
+        );
+    }
+}
+
+/// Encode `data` with [`AnyLenCodec::encode_into`] into a slice of exactly
+/// `max_compressed_len` words, and assert it matches [`AnyLenCodec::encode`].
+pub fn encode_into_matches<T: Element, C: AnyLenCodec<T>>(data: &[T]) {
+    let mut codec = C::default();
+    let mut expected = Vec::new();
+    codec.encode(data, &mut expected).unwrap();
+    let mut out = vec![0; codec.max_compressed_len(data.len())];
+    let written = C::default().encode_into(data, &mut out).unwrap();
+    assert_eq!(out[..written], expected);
 }
 
 pub fn block_roundtrip<C: BlockCodec>(data: &[u32]) {
@@ -79,8 +114,18 @@ pub fn decompress<C: AnyLenCodec>(
     Ok(decompressed)
 }
 
+/// Like [`decompress`], for codecs over a non-default [`Element`] type.
+pub fn decompress_elements<T: Element, C: AnyLenCodec<T>>(
+    compressed: &[u32],
+    expected_len: Option<u32>,
+) -> FastPForResult<Vec<T>> {
+    let mut decompressed = Vec::new();
+    C::default().decode(compressed, &mut decompressed, expected_len)?;
+    Ok(decompressed)
+}
+
 pub fn block_compress<C: BlockCodec>(data: &[u32]) -> FastPForResult<Vec<u32>> {
-    let (blocks, remainder) = slice_to_blocks::<C>(data);
+    let (blocks, remainder) = slice_to_blocks_of::<C, _>(data);
     assert_eq!(
         remainder.len(),
         0,
@@ -100,20 +145,6 @@ pub fn block_decompress<C: BlockCodec>(
     Ok(out)
 }
 
-#[cfg(feature = "cpp")]
-pub fn compress64<C: BlockCodec64 + Default>(data: &[u64]) -> FastPForResult<Vec<u32>> {
-    let mut compressed = Vec::new();
-    C::default().encode64(data, &mut compressed)?;
-    Ok(compressed)
-}
-
-#[cfg(feature = "cpp")]
-pub fn decompress64<C: BlockCodec64 + Default>(compressed: &[u32]) -> FastPForResult<Vec<u64>> {
-    let mut out = Vec::new();
-    C::default().decode64(compressed, &mut out)?;
-    Ok(out)
-}
-
 /// Run [`roundtrip`] for every pure-Rust any-length codec covered here (and optionally C++).
 #[cfg(feature = "rust")]
 pub fn roundtrip_all(data: &[u32]) {
@@ -121,6 +152,14 @@ pub fn roundtrip_all(data: &[u32]) {
     roundtrip::<JustCopy>(data);
     roundtrip::<FastPFor256>(data);
     roundtrip::<FastPFor128>(data);
+    roundtrip_into::<u32, VariableByte>(data);
+    roundtrip_into::<u32, JustCopy>(data);
+    roundtrip_into::<u32, FastPFor256>(data);
+    roundtrip_into::<u32, FastPFor128>(data);
+    encode_into_matches::<u32, VariableByte>(data);
+    encode_into_matches::<u32, JustCopy>(data);
+    encode_into_matches::<u32, FastPFor256>(data);
+    encode_into_matches::<u32, FastPFor128>(data);
 
     #[cfg(feature = "cpp")]
     {
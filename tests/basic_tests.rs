@@ -53,3 +53,33 @@ fn random_roundtrip() {
         roundtrip_all(&data);
     }
 }
+
+/// Codecs chosen at runtime through `Box<dyn DynCodec>` round-trip like their concrete types.
+#[test]
+fn dyn_codec_roundtrip() {
+    use fastpfor::{DynCodec, FastPFor128, FastPFor256, JustCopy, VariableByte};
+
+    let mut codecs: Vec<Box<dyn DynCodec>> = vec![
+        Box::new(FastPFor256::default()),
+        Box::new(FastPFor128::default()),
+        Box::new(VariableByte::new()),
+        Box::new(JustCopy::new()),
+    ];
+    #[cfg(feature = "cpp")]
+    codecs.push(Box::new(fastpfor::cpp::CppFastPFor128::new()));
+
+    let data: Vec<u32> = (0..1000u32).map(|i| i * 31 % 997).collect();
+    for codec in &mut codecs {
+        let mut encoded = Vec::new();
+        codec.encode_dyn(&data, &mut encoded).unwrap();
+        let mut decoded = Vec::new();
+        codec
+            .decode_dyn(&encoded, &mut decoded, Some(1000))
+            .unwrap();
+        assert_eq!(decoded, data);
+
+        let mut slice = vec![0; 1000];
+        assert_eq!(codec.decode_into_dyn(&encoded, &mut slice).unwrap(), 1000);
+        assert_eq!(slice, data);
+    }
+}
@@ -0,0 +1,73 @@
+//! Integration tests for the byte-oriented `encode_to_bytes` / `decode_from_bytes` API.
+
+#![cfg(feature = "rust")]
+
+use fastpfor::{
+    AnyLenCodec, DynCodec, FastPFor128, FastPFor256, FastPForError, JustCopy, VariableByte,
+};
+
+fn bytes_roundtrip<C: AnyLenCodec>(data: &[u32]) {
+    let mut bytes = Vec::new();
+    C::default().encode_to_bytes(data, &mut bytes).unwrap();
+
+    let mut words = Vec::new();
+    C::default().encode(data, &mut words).unwrap();
+    let expected: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+    assert_eq!(bytes, expected, "words must be serialised little-endian");
+
+    let mut decoded = Vec::new();
+    C::default()
+        .decode_from_bytes(&bytes, &mut decoded, Some(data.len() as u32))
+        .unwrap();
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn bytes_roundtrip_all_codecs() {
+    let data: Vec<u32> = (0..1000u32)
+        .map(|i| i.wrapping_mul(2_654_435_761) % 100_000)
+        .collect();
+    bytes_roundtrip::<FastPFor256>(&data);
+    bytes_roundtrip::<FastPFor128>(&data);
+    bytes_roundtrip::<VariableByte>(&data);
+    bytes_roundtrip::<JustCopy>(&data);
+    bytes_roundtrip::<FastPFor256>(&[]);
+}
+
+/// Input that starts at an odd address still decodes.
+#[test]
+fn decode_from_unaligned_bytes() {
+    let data: Vec<u32> = (0..600).collect();
+    let mut bytes = vec![0xAA];
+    FastPFor256::default()
+        .encode_to_bytes(&data, &mut bytes)
+        .unwrap();
+
+    let mut decoded = Vec::new();
+    FastPFor256::default()
+        .decode_from_bytes(&bytes[1..], &mut decoded, None)
+        .unwrap();
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn decode_from_bytes_rejects_partial_words() {
+    let mut decoded: Vec<u32> = Vec::new();
+    let err = VariableByte
+        .decode_from_bytes(&[0x81, 0, 0], &mut decoded, None)
+        .unwrap_err();
+    assert!(matches!(err, FastPForError::InvalidInputLength(3)));
+}
+
+#[test]
+fn dyn_codec_bytes_roundtrip() {
+    let data: Vec<u32> = (0..300).collect();
+    let mut codec: Box<dyn DynCodec> = Box::new(FastPFor128::default());
+    let mut bytes = Vec::new();
+    codec.encode_to_bytes_dyn(&data, &mut bytes).unwrap();
+    let mut decoded = Vec::new();
+    codec
+        .decode_from_bytes_dyn(&bytes, &mut decoded, Some(300))
+        .unwrap();
+    assert_eq!(decoded, data);
+}
@@ -11,7 +11,7 @@
 mod test_utils;
 
 use bytemuck::cast_slice_mut;
-use fastpfor::{FastPFor128, FastPForBlock128};
+use fastpfor::{AnyLenCodec, FastPFor128, FastPForBlock128, VariableByte};
 
 use crate::test_utils::{block_compress, decompress};
 
@@ -225,3 +225,32 @@ fn decode_returns_error_when_exception_position_out_of_block() {
     }
     decompress::<FastPFor128>(&buf, Some(128)).unwrap_err();
 }
+
+#[test]
+fn validate_accepts_only_streams_that_decode() {
+    let data: Vec<u32> = (0..1000u32)
+        .map(|i| i.wrapping_mul(2_654_435_761) >> (i % 32))
+        .collect();
+    let mut codec = FastPFor128::new(FastPForBlock128::new(256).unwrap(), VariableByte::new());
+    let mut encoded = Vec::new();
+    codec.encode(&data, &mut encoded).unwrap();
+    let info = codec.validate(&encoded).unwrap();
+    assert_eq!(info.value_count(), 1000);
+    assert_eq!(info.page_count(), 4);
+    assert_eq!(info.consumed_words(), encoded.len());
+
+    for i in 0..encoded.len() {
+        for flip in [1, 1 << 7, 1 << 16, u32::MAX] {
+            let mut corrupted = encoded.clone();
+            corrupted[i] ^= flip;
+            let Ok(info) = codec.validate(&corrupted) else {
+                continue;
+            };
+            let mut decoded = Vec::new();
+            codec
+                .decode(&corrupted, &mut decoded, None)
+                .unwrap_or_else(|e| panic!("word {i} ^ {flip:#x} validated but failed: {e}"));
+            assert_eq!(decoded.len(), info.value_count());
+        }
+    }
+}
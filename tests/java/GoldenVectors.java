@@ -0,0 +1,73 @@
+import java.util.Arrays;
+
+import me.lemire.integercompression.Composition;
+import me.lemire.integercompression.FastPFOR;
+import me.lemire.integercompression.IntWrapper;
+import me.lemire.integercompression.IntegerCODEC;
+import me.lemire.integercompression.VariableByte;
+
+/**
+ * Writes `tests/java/golden.rs`, the golden vectors of `src/rust/java.rs`.
+ *
+ * <pre>
+ * javac -cp JavaFastPFOR-0.1.12.jar -d target/java tests/java/GoldenVectors.java
+ * java -cp JavaFastPFOR-0.1.12.jar:target/java GoldenVectors &gt; tests/java/golden.rs
+ * </pre>
+ */
+public class GoldenVectors {
+    /** Same as `pages_data` in `src/rust/java.rs`. */
+    static int[] pagesData() {
+        int[] data = new int[2 * 65536 + 40_000 + 67];
+        int tailStart = data.length / 256 * 256;
+        for (int i = 0; i < data.length; ++i) {
+            int block = i / 256;
+            int pos = i % 256;
+            int page = i / 65536;
+            if (i >= tailStart) {
+                data[i] = i * 37 % 100_000;
+            } else if (block % 3 == 0) {
+                // One exception per block, of a width only used in this page:
+                // Java packs leftovers of earlier pages into the unused bits
+                // of an exception width's last word, Rust packs zeros.
+                int width = 2 + 10 * page + block / 3 % 10 + (page == 2 ? 1 : 0);
+                int high = 1 << (width - 1);
+                data[i] = pos == 7 ? high | (i & (high - 1)) : 0;
+            } else if (block % 3 == 1) {
+                data[i] = block % 30 == 1 ? i % 5 : 0;
+            } else {
+                data[i] = pos == 100 ? 1 : 0;
+            }
+        }
+        return data;
+    }
+
+    static int[] encode(IntegerCODEC codec, int[] in) {
+        int[] out = new int[2 * in.length + 1024];
+        IntWrapper outpos = new IntWrapper(0);
+        codec.compress(in, new IntWrapper(0), in.length, out, outpos);
+        return Arrays.copyOf(out, outpos.get());
+    }
+
+    static IntegerCODEC composition() {
+        return new Composition(new FastPFOR(), new VariableByte());
+    }
+
+    static void print(String name, int[] words) {
+        System.out.printf("const %s: [u32; %d] = [%n", name, words.length);
+        for (int i = 0; i < words.length; i += 6) {
+            StringBuilder line = new StringBuilder("   ");
+            for (int j = i; j < Math.min(i + 6, words.length); ++j) {
+                line.append(String.format(" 0x%04X_%04X,", words[j] >>> 16, words[j] & 0xFFFF));
+            }
+            System.out.println(line);
+        }
+        System.out.println("];");
+    }
+
+    public static void main(String[] args) {
+        System.out.println("// Generated by tests/java/GoldenVectors.java, do not edit.");
+        print("GOLDEN_PAGES", encode(composition(), pagesData()));
+        print("GOLDEN_TAIL_ONLY", encode(composition(), new int[] {1, 300}));
+        print("GOLDEN_VARIABLE_BYTE", encode(new VariableByte(), new int[] {1, 300, 5, 1}));
+    }
+}
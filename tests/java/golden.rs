@@ -0,0 +1,237 @@
+// Generated by tests/java/GoldenVectors.java, do not edit.
+const GOLDEN_PAGES: [u32; 1367] = [
+    0x0002_9C00, 0x0000_00D9, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223,
+    0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x888D_111A,
+    0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344, 0x4468_88D1,
+    0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688,
+    0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223,
+    0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x888D_111A,
+    0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344, 0x4468_88D1,
+    0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688,
+    0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223,
+    0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x888D_111A,
+    0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344, 0x4468_88D1,
+    0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688,
+    0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223,
+    0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x888D_111A,
+    0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344, 0x4468_88D1,
+    0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688,
+    0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223,
+    0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x888D_111A,
+    0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344, 0x4468_88D1,
+    0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688,
+    0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223,
+    0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x888D_111A,
+    0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344, 0x4468_88D1,
+    0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688,
+    0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223,
+    0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x888D_111A,
+    0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344, 0x4468_88D1,
+    0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688,
+    0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223,
+    0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x888D_111A,
+    0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344, 0x4468_88D1,
+    0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688,
+    0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223,
+    0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x888D_111A,
+    0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344, 0x4468_88D1,
+    0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688,
+    0x88D1_11A2, 0x2234_4468, 0x0000_0356, 0x0001_0207, 0x0300_0001, 0x0164_0001,
+    0x0307_0000, 0x0001_0164, 0x0001_0407, 0x0000_0001, 0x0164_0001, 0x0507_0000,
+    0x0001_0164, 0x0001_0607, 0x0000_0001, 0x0164_0001, 0x0707_0000, 0x0001_0164,
+    0x0001_0807, 0x0000_0001, 0x0164_0001, 0x0907_0000, 0x0001_0164, 0x0001_0A07,
+    0x0000_0001, 0x0164_0001, 0x0B07_0000, 0x0001_0164, 0x0001_0207, 0x0300_0001,
+    0x0164_0001, 0x0307_0000, 0x0001_0164, 0x0001_0407, 0x0000_0001, 0x0164_0001,
+    0x0507_0000, 0x0001_0164, 0x0001_0607, 0x0000_0001, 0x0164_0001, 0x0707_0000,
+    0x0001_0164, 0x0001_0807, 0x0000_0001, 0x0164_0001, 0x0907_0000, 0x0001_0164,
+    0x0001_0A07, 0x0000_0001, 0x0164_0001, 0x0B07_0000, 0x0001_0164, 0x0001_0207,
+    0x0300_0001, 0x0164_0001, 0x0307_0000, 0x0001_0164, 0x0001_0407, 0x0000_0001,
+    0x0164_0001, 0x0507_0000, 0x0001_0164, 0x0001_0607, 0x0000_0001, 0x0164_0001,
+    0x0707_0000, 0x0001_0164, 0x0001_0807, 0x0000_0001, 0x0164_0001, 0x0907_0000,
+    0x0001_0164, 0x0001_0A07, 0x0000_0001, 0x0164_0001, 0x0B07_0000, 0x0001_0164,
+    0x0001_0207, 0x0300_0001, 0x0164_0001, 0x0307_0000, 0x0001_0164, 0x0001_0407,
+    0x0000_0001, 0x0164_0001, 0x0507_0000, 0x0001_0164, 0x0001_0607, 0x0000_0001,
+    0x0164_0001, 0x0707_0000, 0x0001_0164, 0x0001_0807, 0x0000_0001, 0x0164_0001,
+    0x0907_0000, 0x0001_0164, 0x0001_0A07, 0x0000_0001, 0x0164_0001, 0x0B07_0000,
+    0x0001_0164, 0x0001_0207, 0x0300_0001, 0x0164_0001, 0x0307_0000, 0x0001_0164,
+    0x0001_0407, 0x0000_0001, 0x0164_0001, 0x0507_0000, 0x0001_0164, 0x0001_0607,
+    0x0000_0001, 0x0164_0001, 0x0707_0000, 0x0001_0164, 0x0001_0807, 0x0000_0001,
+    0x0164_0001, 0x0907_0000, 0x0001_0164, 0x0001_0A07, 0x0000_0001, 0x0164_0001,
+    0x0B07_0000, 0x0001_0164, 0x0001_0207, 0x0300_0001, 0x0164_0001, 0x0307_0000,
+    0x0001_0164, 0x0001_0407, 0x0000_0001, 0x0164_0001, 0x0507_0000, 0x0001_0164,
+    0x0001_0607, 0x0000_0001, 0x0164_0001, 0x0707_0000, 0x0001_0164, 0x0001_0807,
+    0x0000_0001, 0x0164_0001, 0x0907_0000, 0x0001_0164, 0x0001_0A07, 0x0000_0001,
+    0x0164_0001, 0x0B07_0000, 0x0001_0164, 0x0001_0207, 0x0300_0001, 0x0164_0001,
+    0x0307_0000, 0x0001_0164, 0x0001_0407, 0x0000_0001, 0x0164_0001, 0x0507_0000,
+    0x0001_0164, 0x0001_0607, 0x0000_0001, 0x0164_0001, 0x0707_0000, 0x0001_0164,
+    0x0001_0807, 0x0000_0001, 0x0164_0001, 0x0907_0000, 0x0001_0164, 0x0001_0A07,
+    0x0000_0001, 0x0164_0001, 0x0B07_0000, 0x0001_0164, 0x0001_0207, 0x0300_0001,
+    0x0164_0001, 0x0307_0000, 0x0001_0164, 0x0001_0407, 0x0000_0001, 0x0164_0001,
+    0x0507_0000, 0x0001_0164, 0x0001_0607, 0x0000_0001, 0x0164_0001, 0x0707_0000,
+    0x0001_0164, 0x0001_0807, 0x0000_0001, 0x0164_0001, 0x0907_0000, 0x0001_0164,
+    0x0001_0A07, 0x0000_0001, 0x0164_0001, 0x0B07_0000, 0x0001_0164, 0x0001_0207,
+    0x0300_0001, 0x0164_0001, 0x0307_0000, 0x0001_0164, 0x0001_0407, 0x0000_0001,
+    0x0164_0001, 0x0507_0000, 0x0001_0164, 0x0001_0607, 0x0000_0001, 0x0164_0001,
+    0x0707_0000, 0x0000_07FE, 0x0000_0009, 0x0003_FFFF, 0x0000_0009, 0x07FF_FFFF,
+    0x0000_0009, 0xFFFF_FFFF, 0x0000_000F, 0x0000_0009, 0xEF7B_DEF7, 0x0000_17BD,
+    0x0000_0009, 0xE79E_79E7, 0x0027_9E79, 0x0000_0009, 0x78F1_E3C7, 0x478F_1E3C,
+    0x0000_0008, 0x8787_8787, 0x8787_8787, 0x0000_0008, 0x3C1E_0F07, 0xC1E0_F078,
+    0x0000_0083, 0x0000_0008, 0xE078_1E07, 0x781E_0781, 0x0000_81E0, 0x0000_0008,
+    0xC1E8_3F07, 0x83F0_7A0F, 0x00A0_FC1E, 0x0000_00D9, 0x4468_88D1, 0x111A_2234,
+    0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2,
+    0x2234_4468, 0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11,
+    0x11A2_2344, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888,
+    0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234,
+    0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2,
+    0x2234_4468, 0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11,
+    0x11A2_2344, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888,
+    0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234,
+    0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2,
+    0x2234_4468, 0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11,
+    0x11A2_2344, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888,
+    0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234,
+    0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2,
+    0x2234_4468, 0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11,
+    0x11A2_2344, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888,
+    0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234,
+    0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2,
+    0x2234_4468, 0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11,
+    0x11A2_2344, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888,
+    0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234,
+    0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2,
+    0x2234_4468, 0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11,
+    0x11A2_2344, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888,
+    0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234,
+    0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2,
+    0x2234_4468, 0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11,
+    0x11A2_2344, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888,
+    0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234,
+    0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2,
+    0x2234_4468, 0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11,
+    0x11A2_2344, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888,
+    0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234,
+    0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2,
+    0x2234_4468, 0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11,
+    0x11A2_2344, 0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888,
+    0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x0000_0354, 0x0000_0001,
+    0x0164_0001, 0x1207_0000, 0x0001_0164, 0x0001_1307, 0x0000_0001, 0x0164_0001,
+    0x1407_0000, 0x0001_0164, 0x0001_1507, 0x0000_0001, 0x0164_0001, 0x0C07_0300,
+    0x0001_0164, 0x0001_0D07, 0x0000_0001, 0x0164_0001, 0x0E07_0000, 0x0001_0164,
+    0x0001_0F07, 0x0000_0001, 0x0164_0001, 0x1007_0000, 0x0001_0164, 0x0001_1107,
+    0x0000_0001, 0x0164_0001, 0x1207_0000, 0x0001_0164, 0x0001_1307, 0x0000_0001,
+    0x0164_0001, 0x1407_0000, 0x0001_0164, 0x0001_1507, 0x0000_0001, 0x0164_0001,
+    0x0C07_0300, 0x0001_0164, 0x0001_0D07, 0x0000_0001, 0x0164_0001, 0x0E07_0000,
+    0x0001_0164, 0x0001_0F07, 0x0000_0001, 0x0164_0001, 0x1007_0000, 0x0001_0164,
+    0x0001_1107, 0x0000_0001, 0x0164_0001, 0x1207_0000, 0x0001_0164, 0x0001_1307,
+    0x0000_0001, 0x0164_0001, 0x1407_0000, 0x0001_0164, 0x0001_1507, 0x0000_0001,
+    0x0164_0001, 0x0C07_0300, 0x0001_0164, 0x0001_0D07, 0x0000_0001, 0x0164_0001,
+    0x0E07_0000, 0x0001_0164, 0x0001_0F07, 0x0000_0001, 0x0164_0001, 0x1007_0000,
+    0x0001_0164, 0x0001_1107, 0x0000_0001, 0x0164_0001, 0x1207_0000, 0x0001_0164,
+    0x0001_1307, 0x0000_0001, 0x0164_0001, 0x1407_0000, 0x0001_0164, 0x0001_1507,
+    0x0000_0001, 0x0164_0001, 0x0C07_0300, 0x0001_0164, 0x0001_0D07, 0x0000_0001,
+    0x0164_0001, 0x0E07_0000, 0x0001_0164, 0x0001_0F07, 0x0000_0001, 0x0164_0001,
+    0x1007_0000, 0x0001_0164, 0x0001_1107, 0x0000_0001, 0x0164_0001, 0x1207_0000,
+    0x0001_0164, 0x0001_1307, 0x0000_0001, 0x0164_0001, 0x1407_0000, 0x0001_0164,
+    0x0001_1507, 0x0000_0001, 0x0164_0001, 0x0C07_0300, 0x0001_0164, 0x0001_0D07,
+    0x0000_0001, 0x0164_0001, 0x0E07_0000, 0x0001_0164, 0x0001_0F07, 0x0000_0001,
+    0x0164_0001, 0x1007_0000, 0x0001_0164, 0x0001_1107, 0x0000_0001, 0x0164_0001,
+    0x1207_0000, 0x0001_0164, 0x0001_1307, 0x0000_0001, 0x0164_0001, 0x1407_0000,
+    0x0001_0164, 0x0001_1507, 0x0000_0001, 0x0164_0001, 0x0C07_0300, 0x0001_0164,
+    0x0001_0D07, 0x0000_0001, 0x0164_0001, 0x0E07_0000, 0x0001_0164, 0x0001_0F07,
+    0x0000_0001, 0x0164_0001, 0x1007_0000, 0x0001_0164, 0x0001_1107, 0x0000_0001,
+    0x0164_0001, 0x1207_0000, 0x0001_0164, 0x0001_1307, 0x0000_0001, 0x0164_0001,
+    0x1407_0000, 0x0001_0164, 0x0001_1507, 0x0000_0001, 0x0164_0001, 0x0C07_0300,
+    0x0001_0164, 0x0001_0D07, 0x0000_0001, 0x0164_0001, 0x0E07_0000, 0x0001_0164,
+    0x0001_0F07, 0x0000_0001, 0x0164_0001, 0x1007_0000, 0x0001_0164, 0x0001_1107,
+    0x0000_0001, 0x0164_0001, 0x1207_0000, 0x0001_0164, 0x0001_1307, 0x0000_0001,
+    0x0164_0001, 0x1407_0000, 0x0001_0164, 0x0001_1507, 0x0000_0001, 0x0164_0001,
+    0x0C07_0300, 0x0001_0164, 0x0001_0D07, 0x0000_0001, 0x0164_0001, 0x0E07_0000,
+    0x0001_0164, 0x0001_0F07, 0x0000_0001, 0x0164_0001, 0x1007_0000, 0x0001_0164,
+    0x0001_1107, 0x0000_0001, 0x0164_0001, 0x1207_0000, 0x0001_0164, 0x0001_1307,
+    0x0000_0001, 0x0164_0001, 0x1407_0000, 0x0001_0164, 0x0001_1507, 0x0000_0001,
+    0x0164_0001, 0x0C07_0300, 0x001F_F800, 0x0000_0009, 0x07C0_7E07, 0x7E07_807A,
+    0x807A_07C0, 0x0000_0E07, 0x0000_0008, 0x1FE0_F107, 0x907D_83F4, 0x3D41_EE0F,
+    0x0000_0098, 0x0000_0008, 0x7C81_F407, 0x07B8_1F00, 0x807A_81EC, 0x0000_981E,
+    0x0000_0008, 0xFA83_D707, 0x7E20_F4C1, 0x1F68_3CF0, 0x00D2_0F2C, 0x0000_0008,
+    0xB807_9A07, 0xF407_D607, 0xB007_9207, 0xEC07_CE07, 0x0000_0008, 0x760F_1D07,
+    0xB83D_641E, 0x60F9_507B, 0x83F4_41F6, 0x0000_00F7, 0x0000_0009, 0x801F_0207,
+    0x01F3_E07C, 0x1F7A_07D7, 0xFB60_7E60, 0xF207_F501, 0x0000_0003, 0x0000_0009,
+    0x183D_0507, 0x0F50_41E9, 0xD7D0_7ABE, 0xE41E_CD83, 0x07BA_E0F6, 0x0000_05F5,
+    0x0000_0009, 0x6079_0807, 0x7944_0792, 0x8007_9620, 0x0799_E079, 0x9DA0_79BC,
+    0x0009_F807, 0x0000_0009, 0x20F1_0B07, 0xC51C_1E25, 0x3078_B283, 0xE342_0F18,
+    0xE83C_6FC1, 0x11FB_078E, 0x0000_0079, 0x4468_88D1, 0x111A_2234, 0x4446_888D,
+    0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468,
+    0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344,
+    0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22,
+    0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D,
+    0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468,
+    0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344,
+    0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22,
+    0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D,
+    0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468,
+    0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344,
+    0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22,
+    0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D,
+    0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468,
+    0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344,
+    0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22,
+    0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x4468_88D1, 0x111A_2234, 0x4446_888D,
+    0xD111_A223, 0x3444_6888, 0x8D11_1A22, 0x2344_4688, 0x88D1_11A2, 0x2234_4468,
+    0x888D_111A, 0xA223_4446, 0x6888_D111, 0x1A22_3444, 0x4688_8D11, 0x11A2_2344,
+    0x4468_88D1, 0x111A_2234, 0x4446_888D, 0xD111_A223, 0x3444_6888, 0x8D11_1A22,
+    0x2344_4688, 0x88D1_11A2, 0x2234_4468, 0x0000_0208, 0x0001_0164, 0x0001_1807,
+    0x0000_0001, 0x0164_0001, 0x1907_0000, 0x0001_0164, 0x0001_1A07, 0x0000_0001,
+    0x0164_0001, 0x1B07_0000, 0x0001_0164, 0x0001_1C07, 0x0000_0001, 0x0164_0001,
+    0x1D07_0000, 0x0001_0164, 0x0001_1E07, 0x0000_0001, 0x0164_0001, 0x1F07_0000,
+    0x0001_0164, 0x0001_2007, 0x0000_0001, 0x0164_0001, 0x1707_0300, 0x0001_0164,
+    0x0001_1807, 0x0000_0001, 0x0164_0001, 0x1907_0000, 0x0001_0164, 0x0001_1A07,
+    0x0000_0001, 0x0164_0001, 0x1B07_0000, 0x0001_0164, 0x0001_1C07, 0x0000_0001,
+    0x0164_0001, 0x1D07_0000, 0x0001_0164, 0x0001_1E07, 0x0000_0001, 0x0164_0001,
+    0x1F07_0000, 0x0001_0164, 0x0001_2007, 0x0000_0001, 0x0164_0001, 0x1707_0300,
+    0x0001_0164, 0x0001_1807, 0x0000_0001, 0x0164_0001, 0x1907_0000, 0x0001_0164,
+    0x0001_1A07, 0x0000_0001, 0x0164_0001, 0x1B07_0000, 0x0001_0164, 0x0001_1C07,
+    0x0000_0001, 0x0164_0001, 0x1D07_0000, 0x0001_0164, 0x0001_1E07, 0x0000_0001,
+    0x0164_0001, 0x1F07_0000, 0x0001_0164, 0x0001_2007, 0x0000_0001, 0x0164_0001,
+    0x1707_0300, 0x0001_0164, 0x0001_1807, 0x0000_0001, 0x0164_0001, 0x1907_0000,
+    0x0001_0164, 0x0001_1A07, 0x0000_0001, 0x0164_0001, 0x1B07_0000, 0x0001_0164,
+    0x0001_1C07, 0x0000_0001, 0x0164_0001, 0x1D07_0000, 0x0001_0164, 0x0001_1E07,
+    0x0000_0001, 0x0164_0001, 0x1F07_0000, 0x0001_0164, 0x0001_2007, 0x0000_0001,
+    0x0164_0001, 0x1707_0300, 0x0001_0164, 0x0001_1807, 0x0000_0001, 0x0164_0001,
+    0x1907_0000, 0x0001_0164, 0x0001_1A07, 0x0000_0001, 0x0164_0001, 0x1B07_0000,
+    0x0001_0164, 0x0001_1C07, 0x0000_0001, 0x0164_0001, 0x1D07_0000, 0x0001_0164,
+    0x0001_1E07, 0x0000_0001, 0x0164_0001, 0x1F07_0000, 0x0001_0164, 0x0001_2007,
+    0x0000_0001, 0x0164_0001, 0x1707_0300, 0x0001_0164, 0x0001_1807, 0x0000_0001,
+    0x0164_0001, 0x1907_0000, 0xFFC0_0000, 0x0000_0005, 0x03C2_1C07, 0x9601_E11D,
+    0x784E_C0F0, 0x0004_2940, 0x0000_0006, 0x0782_0107, 0x3D07_821F, 0x825B_0782,
+    0x0782_7907, 0x0000_8297, 0x0000_0006, 0x0F02_0407, 0x001E_0444, 0x12F0_3C09,
+    0xF027_C078, 0x0020_5340, 0x0000_0005, 0x1E02_0707, 0x3078_0894, 0x9841_E024,
+    0x027F_0780, 0x0000_0002, 0x0000_0005, 0x3C02_0A07, 0x81E0_1140, 0xC80F_0091,
+    0x2820_7804, 0x0000_0040, 0x0000_0005, 0x7802_0D07, 0x0780_22B0, 0x7078_0249,
+    0x8507_8026, 0x0000_0802, 0x0000_0005, 0xF002_1007, 0x1E00_45C0, 0x03C0_0930,
+    0x8078_0135, 0x0001_0028, 0x0000_0005, 0xE002_1307, 0x7800_8C41, 0x1E00_24F0,
+    0x0780_09B4, 0x0020_028B, 0x0000_0005, 0xC002_1607, 0xE001_1A03, 0xF000_9481,
+    0x7800_4E00, 0x0400_28E0, 0x0000_0005, 0x8002_1907, 0x8002_3707, 0x8002_5507,
+    0x8002_7307, 0x8002_9107, 0x2055_8145, 0x5581_6A55, 0x810F_5681, 0x3456_8159,
+    0x5681_7E56, 0x8123_5781, 0x4857_816D, 0x5781_1258, 0x8137_5881, 0x5C58_8101,
+    0x5981_2659, 0x814B_5981, 0x7059_8115, 0x5A81_3A5A, 0x815F_5A81, 0x045B_8129,
+    0x5B81_4E5B, 0x8173_5B81, 0x185C_813D, 0x5C81_625C, 0x8107_5D81, 0x2C5D_8151,
+    0x5D81_765D, 0x811B_5E81, 0x405E_8165, 0x5E81_0A5F, 0x812F_5F81, 0x545F_8179,
+    0x5F81_1E60, 0x8143_6081, 0x6860_810D, 0x6181_3261, 0x8157_6181, 0x7C61_8121,
+    0x6281_4662, 0x816B_6281, 0x1063_8135, 0x6381_5A63, 0x817F_6381, 0x2464_8149,
+    0x6481_6E64, 0x8113_6581, 0x3865_815D, 0x6581_0266, 0x8127_6681, 0x4C66_8171,
+    0x6681_1667, 0x813B_6781, 0x6067_8105, 0x6881_2A68, 0x814F_6881, 0x7468_8119,
+    0x6981_3E69, 0x8163_6981, 0x086A_812D, 0x6A81_526A, 0x8177_6A81, 0x1C6B_8141,
+    0x6B81_666B, 0x810B_6C81, 0x306C_8155, 0x6C81_7A6C, 0x811F_6D81, 0x446D_8169,
+    0x6D81_0E6E, 0x8133_6E81, 0x586E_817D, 0x6E81_226F, 0x8147_6F81, 0x6C6F_8111,
+    0x7081_3670, 0x815B_7081, 0x0071_8125, 0x7181_4A71, 0x816F_7181, 0x1472_8139,
+    0x7281_5E72, 0x8103_7381, 0x2873_814D, 0x7381_7273, 0x8117_7481, 0x3C74_8161,
+    0x7481_0675, 0x812B_7581, 0x5075_8175, 0x7581_1A76, 0x813F_7681, 0x6476_8109,
+    0x7781_2E77, 0x8153_7781, 0x7877_811D, 0x7881_4278, 0x8167_7881, 0x0C79_8131,
+    0x7981_5679, 0x817B_7981, 0x207A_8145, 0x7A81_6A7A, 0x8100_0000,
+];
+const GOLDEN_TAIL_ONLY: [u32; 2] = [
+    0x0000_0000, 0x812C_8200,
+];
+const GOLDEN_VARIABLE_BYTE: [u32; 2] = [
+    0x812C_8285, 0x8100_0000,
+];